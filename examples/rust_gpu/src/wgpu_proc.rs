@@ -2,6 +2,7 @@ use wgpu::*;
 use parking_lot::RwLock;
 use std::collections::HashMap;
 use std::sync::atomic::AtomicUsize;
+use std::sync::OnceLock;
 
 pub struct BufferState {
     pub in_size: (usize, usize, usize),
@@ -15,10 +16,44 @@ pub struct BufferState {
     pub last_access: AtomicUsize
 }
 
-pub struct WgpuProcessing<T: Sized> {
-    _adapter: Adapter,
+/// Adapter, device and queue shared by all [`WgpuProcessing`] instances in the process.
+///
+/// Creating a device is expensive and every device holds its own VRAM, so it's created once on first use and never dropped.
+/// wgpu's `Device` and `Queue` are `Send + Sync`, so the context can be used from any render thread. Everything that is
+/// mutated during a render (textures, buffers, bind groups) is kept per instance and per thread in [`WgpuProcessing`].
+pub struct WgpuContext {
+    pub adapter: Adapter,
     pub device: Device,
     pub queue: Queue,
+}
+
+impl WgpuContext {
+    pub fn shared() -> &'static WgpuContext {
+        static CONTEXT: OnceLock<WgpuContext> = OnceLock::new();
+        CONTEXT.get_or_init(|| {
+            let power_preference = util::power_preference_from_env().unwrap_or(PowerPreference::HighPerformance);
+            let instance = Instance::new(InstanceDescriptor::default());
+
+            let adapter = pollster::block_on(instance.request_adapter(&RequestAdapterOptions { power_preference, ..Default::default() })).unwrap();
+
+            let (device, queue) = pollster::block_on(
+                adapter.request_device(&DeviceDescriptor {
+                    label: None,
+                    required_features: adapter.features(),
+                    required_limits: adapter.limits()
+                }, None)
+            ).unwrap();
+
+            let info = adapter.get_info();
+            log::info!("Using {} ({}) - {:#?}.", info.name, info.device, info.backend);
+
+            Self { adapter, device, queue }
+        })
+    }
+}
+
+pub struct WgpuProcessing<T: Sized> {
+    pub context: &'static WgpuContext,
     pub shader: ShaderModule,
     pub pipeline: ComputePipeline,
     pub state: RwLock<HashMap<std::thread::ThreadId, BufferState>>,
@@ -33,21 +68,8 @@ pub enum ProcShaderSource<'a> {
 
 impl<T: Sized> WgpuProcessing<T> {
     pub fn new(shader: ProcShaderSource) -> Self {
-        let power_preference = util::power_preference_from_env().unwrap_or(PowerPreference::HighPerformance);
-        let instance = Instance::new(InstanceDescriptor::default());
-
-        let adapter = pollster::block_on(instance.request_adapter(&RequestAdapterOptions { power_preference, ..Default::default() })).unwrap();
-
-        let (device, queue) = pollster::block_on(
-            adapter.request_device(&DeviceDescriptor {
-                label: None,
-                required_features: adapter.features(),
-                required_limits: adapter.limits()
-            }, None)
-        ).unwrap();
-
-        let info = adapter.get_info();
-        log::info!("Using {} ({}) - {:#?}.", info.name, info.device, info.backend);
+        let context = WgpuContext::shared();
+        let (device, queue) = (&context.device, &context.queue);
 
         let shader = device.create_shader_module(ShaderModuleDescriptor {
             label: None,
@@ -80,9 +102,7 @@ impl<T: Sized> WgpuProcessing<T> {
         });
 
         Self {
-            _adapter: adapter,
-            device,
-            queue,
+            context,
             shader,
             pipeline,
             _marker: std::marker::PhantomData,
@@ -120,9 +140,9 @@ impl<T: Sized> WgpuProcessing<T> {
             view_formats: &[]
         };
 
-        let in_texture = self.device.create_texture(&in_desc);
-        let out_texture = self.device.create_texture(&out_desc);
-        let staging_buffer = self.device.create_buffer(&BufferDescriptor {
+        let in_texture = self.context.device.create_texture(&in_desc);
+        let out_texture = self.context.device.create_texture(&out_desc);
+        let staging_buffer = self.context.device.create_buffer(&BufferDescriptor {
             size: staging_size as u64,
             usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
             label: None,
@@ -132,14 +152,14 @@ impl<T: Sized> WgpuProcessing<T> {
         let in_view = in_texture.create_view(&TextureViewDescriptor::default());
         let out_view = out_texture.create_view(&TextureViewDescriptor::default());
 
-        let params = self.device.create_buffer(&BufferDescriptor {
+        let params = self.context.device.create_buffer(&BufferDescriptor {
             size: std::mem::size_of::<T>() as u64,
             usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
             label: None,
             mapped_at_creation: false
         });
 
-        let bind_group = self.device.create_bind_group(&BindGroupDescriptor {
+        let bind_group = self.context.device.create_bind_group(&BindGroupDescriptor {
             label: None,
             layout: &self.pipeline.get_bind_group_layout(0),
             entries: &[
@@ -200,17 +220,17 @@ impl<T: Sized> WgpuProcessing<T> {
         let width = out_size.0 as u32;
         let height = out_size.1 as u32;
 
-        let mut encoder = self.device.create_command_encoder(&CommandEncoderDescriptor { label: None });
+        let mut encoder = self.context.device.create_command_encoder(&CommandEncoderDescriptor { label: None });
 
         // Write params uniform
-        self.queue.write_buffer(
+        self.context.queue.write_buffer(
             &state.params,
             0,
             unsafe { std::slice::from_raw_parts(params as *const _ as _, std::mem::size_of::<T>() ) }
         );
 
         // Write input texture
-        self.queue.write_texture(
+        self.context.queue.write_texture(
             state.in_texture.as_image_copy(),
             in_buffer,
             ImageDataLayout { offset: 0, bytes_per_row: Some(in_size.2 as u32), rows_per_image: None },
@@ -232,14 +252,14 @@ impl<T: Sized> WgpuProcessing<T> {
             Extent3d { width: width as u32, height: height as u32, depth_or_array_layers: 1 }
         );
 
-        self.queue.submit(Some(encoder.finish()));
+        self.context.queue.submit(Some(encoder.finish()));
 
         // Read the output buffer
         let buffer_slice = state.staging_buffer.slice(..);
         let (sender, receiver) = futures_intrusive::channel::shared::oneshot_channel();
         buffer_slice.map_async(MapMode::Read, move |v| sender.send(v).unwrap());
 
-        self.device.poll(Maintain::Wait);
+        self.context.device.poll(Maintain::Wait);
 
         if let Some(Ok(())) = pollster::block_on(receiver.receive()) {
             let out_stride = out_size.2;
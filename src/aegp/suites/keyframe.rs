@@ -53,6 +53,16 @@ impl KeyframeSuite {
         Ok(call_suite_fn_single!(self, AEGP_InsertKeyframe -> A_long, stream.as_ptr(), time_mode.into(), &time.into() as *const _)? as i32)
     }
 
+    /// Adds a keyframe like [`insert_keyframe`](Self::insert_keyframe) and sets its value to `value`.
+    ///
+    /// Returns the new keyframe's index.
+    pub fn insert_keyframe_with_value(&self, stream: impl AsPtr<AEGP_StreamRefH>, time_mode: TimeMode, time: Time, value: StreamValue) -> Result<i32, Error> {
+        let stream = stream.as_ptr();
+        let key_index = self.insert_keyframe(stream, time_mode, time)?;
+        self.set_keyframe_value(stream, key_index, value)?;
+        Ok(key_index)
+    }
+
     /// Deletes the specified keyframe.
     pub fn delete_keyframe(&self, stream: impl AsPtr<AEGP_StreamRefH>, key_index: i32) -> Result<(), Error> {
         call_suite_fn!(self, AEGP_DeleteKeyframe, stream.as_ptr(), key_index)
@@ -288,6 +298,11 @@ define_suite_item_wrapper!(
         /// If there is already a keyframe at that time, the values will be updated.
         insert(time_mode: TimeMode, time: Time) -> i32 => suite.insert_keyframe,
 
+        /// Adds a keyframe like [`insert`](Self::insert) and sets its value to `value`.
+        ///
+        /// Returns the new keyframe's index.
+        insert_with_value(time_mode: TimeMode, time: Time, value: StreamValue) -> i32 => suite.insert_keyframe_with_value,
+
         /// Deletes the specified keyframe.
         delete(key_index: i32) -> () => suite.delete_keyframe,
 
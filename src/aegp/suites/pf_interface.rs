@@ -35,6 +35,18 @@ impl PFInterfaceSuite {
         ))
     }
 
+    /// Returns whether the effect is currently enabled on its layer.
+    ///
+    /// This is a shortcut for obtaining the [`EffectRefHandle`] with [`new_effect_for_effect()`](Self::new_effect_for_effect)
+    /// and checking [`EffectFlags::ACTIVE`]. The temporary handle is disposed before returning.
+    pub fn effect_is_active(&self, effect_ref: impl AsPtr<PF_ProgPtr>, plugin_id: PluginId) -> Result<bool, Error> {
+        let effect_suite = aegp::suites::Effect::new()?;
+        let aegp_effect = self.new_effect_for_effect(effect_ref, plugin_id)?;
+        let flags = effect_suite.effect_flags(aegp_effect);
+        effect_suite.dispose_effect(aegp_effect)?;
+        Ok(flags?.contains(EffectFlags::ACTIVE))
+    }
+
     /// Retrieve the composition time corresponding to the effect's layer time.
     pub fn convert_effect_to_comp_time(&self, effect_ref: impl AsPtr<PF_ProgPtr>, time: i32, time_scale: u32) -> Result<Time, Error> {
         Ok(call_suite_fn_single!(self, AEGP_ConvertEffectToCompTime -> ae_sys::A_Time, effect_ref.as_ptr(), time, time_scale)?.into())
@@ -251,6 +251,17 @@ impl UtilitySuite {
             ).to_string_lossy()
         })
     }
+
+    /// Same as [`plugin_paths()`](Self::plugin_paths), as a [`PathBuf`](std::path::PathBuf) for joining with the names of bundled files.
+    ///
+    /// Returns [`Error::Generic`] if the host returns an empty path, e.g. for the unimplemented [`GetPathTypes::Plugin`].
+    pub fn plugin_path(&self, plugin_id: PluginId, path_type: GetPathTypes) -> Result<std::path::PathBuf, Error> {
+        let path = self.plugin_paths(plugin_id, path_type)?;
+        if path.is_empty() {
+            return Err(Error::Generic);
+        }
+        Ok(path.into())
+    }
 }
 
 // ――――――――――――――――――――――――――――――――――――――― Types ――――――――――――――――――――――――――――――――――――――――
@@ -22,6 +22,7 @@ pub use suites::surface::{
     InterpolationPolicy,
     AntiAliasPolicy,
     Surface,
+    SurfaceState,
 };
 
 pub type PointF32  = ae_sys::DRAWBOT_PointF32;
@@ -30,6 +31,27 @@ pub type RectF32   = ae_sys::DRAWBOT_RectF32;
 pub type MatrixF32 = ae_sys::DRAWBOT_MatrixF32;
 pub type Rect32    = ae_sys::DRAWBOT_Rect32;
 
+/// Converts an 8 bit pixel, e.g. the value of a color param, to a drawbot color with channels in 0..1.
+pub fn pixel8_to_color_rgba(p: pf::Pixel8) -> ColorRgba {
+    ColorRgba {
+        red:   p.red   as f32 / 255.0,
+        green: p.green as f32 / 255.0,
+        blue:  p.blue  as f32 / 255.0,
+        alpha: p.alpha as f32 / 255.0,
+    }
+}
+
+/// Converts a drawbot color to an 8 bit pixel, clamping the channels to 0..1.
+pub fn color_rgba_to_pixel8(c: ColorRgba) -> pf::Pixel8 {
+    let to_u8 = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+    pf::Pixel8 {
+        alpha: to_u8(c.alpha),
+        red:   to_u8(c.red),
+        green: to_u8(c.green),
+        blue:  to_u8(c.blue),
+    }
+}
+
 // ――――――――――――――――――――――――――――――――――――――――――――――――――――――――――――――――――――――――――――――――――――
 
 define_suite!(
@@ -71,6 +93,65 @@ impl Drawbot {
         let suite = pf::suites::EffectCustomUIOverlayTheme::new()?;
         suite.fill_vertex(self.handle, center_point, draw_shadow)
     }
+
+    /// Draws the contents of `layer` on the surface with its top-left corner at `origin`. Alpha = [0.0, 1.0].
+    ///
+    /// The pixels are converted to 8 bits per channel and uploaded as a premultiplied image in the layout preferred by the supplier.
+    pub fn draw_layer(&self, layer: &pf::Layer, origin: &PointF32, alpha: f32) -> Result<(), Error> {
+        let supplier = self.supplier()?;
+        let bgra = supplier.prefers_pixel_layout_bgra()? || !supplier.supports_pixel_layout_argb()?;
+        let (width, height) = (layer.width(), layer.height());
+
+        let mut buffer = Vec::with_capacity(width * height * 4);
+        for y in 0..height {
+            for x in 0..width {
+                let p = match layer.world_type() {
+                    aegp::WorldType::U15 => pf::pixel16_to_8(*layer.as_pixel16(x, y)),
+                    aegp::WorldType::F32 => pf::pixel_f32_to_8(*layer.as_pixel32(x, y)),
+                    _ => *layer.as_pixel8(x, y),
+                };
+                if bgra {
+                    buffer.extend_from_slice(&[p.blue, p.green, p.red, p.alpha]);
+                } else {
+                    buffer.extend_from_slice(&[p.alpha, p.red, p.green, p.blue]);
+                }
+            }
+        }
+        let layout = if bgra { PixelLayout::Bgra32Premul } else { PixelLayout::Argb32Premul };
+        let image = supplier.new_image_from_buffer(width, height, width * 4, layout, &buffer)?;
+        self.surface()?.draw_image(&image, origin, alpha)
+    }
+
+    /// Strokes an anti-aliased line from `a` to `b`.
+    ///
+    /// If `width` is `None`, the overlay theme stroke width is used.
+    pub fn draw_line(&self, a: &PointF32, b: &PointF32, color: &ColorRgba, width: Option<f32>) -> Result<(), Error> {
+        let mut path = self.supplier()?.new_path()?;
+        path.move_to(a.x, a.y)?;
+        path.line_to(b.x, b.y)?;
+        self.stroke_anti_aliased(&path, color, width)
+    }
+
+    /// Strokes the outline of an anti-aliased rectangle.
+    ///
+    /// If `width` is `None`, the overlay theme stroke width is used.
+    pub fn draw_rect(&self, rect: &RectF32, color: &ColorRgba, width: Option<f32>) -> Result<(), Error> {
+        let mut path = self.supplier()?.new_path()?;
+        path.add_rect(rect)?;
+        self.stroke_anti_aliased(&path, color, width)
+    }
+
+    fn stroke_anti_aliased(&self, path: &Path, color: &ColorRgba, width: Option<f32>) -> Result<(), Error> {
+        let width = match width {
+            Some(width) => width,
+            None => pf::suites::EffectCustomUIOverlayTheme::new()?.preferred_stroke_width()?,
+        };
+        let pen = self.supplier()?.new_pen(color, width)?;
+        let surface = self.surface()?;
+        let state = surface.push_state()?;
+        state.set_anti_alias_policy(AntiAliasPolicy::High)?;
+        state.stroke_path(&pen, path)
+    }
 }
 impl AsPtr<ae_sys::DRAWBOT_DrawRef> for Drawbot {
     fn as_ptr(&self) -> ae_sys::DRAWBOT_DrawRef {
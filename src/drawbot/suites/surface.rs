@@ -208,3 +208,39 @@ define_suite_item_wrapper!(
         flush() -> () => suite.flush,
     }
 );
+
+impl Surface {
+    /// Pushes the current surface state and returns a guard that pops it again when dropped.
+    ///
+    /// Clip, transform and policy changes made through the guard are undone at the end of its scope, so they can't leak into the next overlay:
+    /// ```ignore
+    /// {
+    ///     let state = surface.push_state()?;
+    ///     state.clip(&supplier, &rect)?;
+    ///     state.transform(&matrix)?;
+    ///     state.stroke_path(&pen, &path)?;
+    /// } // state is popped here
+    /// ```
+    pub fn push_state(&self) -> Result<SurfaceState<'_>, Error> {
+        self.push_state_stack()?;
+        Ok(SurfaceState { surface: self })
+    }
+}
+
+/// A pushed surface state, see [`Surface::push_state()`].
+pub struct SurfaceState<'a> {
+    surface: &'a Surface,
+}
+impl std::ops::Deref for SurfaceState<'_> {
+    type Target = Surface;
+    fn deref(&self) -> &Surface {
+        self.surface
+    }
+}
+impl Drop for SurfaceState<'_> {
+    fn drop(&mut self) {
+        if let Err(e) = self.surface.pop_state_stack() {
+            log::error!("Failed to pop surface state: {e}");
+        }
+    }
+}
@@ -38,6 +38,13 @@
 //!   Contact the Adobe Ae SDK team and ask nicely and they may send you
 //!   theses headers.
 //!
+//! * `image` – Conversion between layers and `image::RgbaImage`, e.g. to
+//!   write frames to disk for debugging.
+//!
+//! * `runtime-checks` – Keep the bounds checks of the pixel accessors
+//!   (`Layer::as_pixel8()` etc.) in release builds, e.g. to hand a checked
+//!   build to a user to track down an out of bounds crash.
+//!
 //! ## Using
 //!
 //! Add `after-effects` to your dependencies.
@@ -121,6 +128,46 @@ pub(crate) fn borrow_pica_basic_as_ptr() -> *const ae_sys::SPBasicSuite {
     pica_basic_ptr
 }
 
+/// Returns `true` if the `PicaBasic` suite has been set for the current thread, i.e. suites can be acquired.
+///
+/// This is the case while the crate is handling a command from the host. Outside of that, e.g. on a thread spawned by the plugin,
+/// acquiring a suite (and everything built on top of one, like [`pf::Handle`]) returns [`Error::InvalidCallback`].
+pub fn is_pica_basic_initialized() -> bool {
+    !borrow_pica_basic_as_ptr().is_null()
+}
+
+/// Cuts `s` at the first nul byte and then to at most `max_len` bytes, on a char boundary.
+pub(crate) fn truncate_str(s: &str, max_len: usize) -> &str {
+    let s = s.split('\0').next().unwrap_or_default();
+    let mut len = s.len().min(max_len);
+    while !s.is_char_boundary(len) {
+        len -= 1;
+    }
+    &s[..len]
+}
+
+/// Copies `s` into the fixed size C string buffer `dst`, truncating it with [`truncate_str`] so that the terminating nul fits.
+///
+/// Returns `false` if `s` had to be truncated.
+pub(crate) fn copy_to_c_buffer(dst: &mut [std::ffi::c_char], s: &str) -> bool {
+    let Some(max_len) = dst.len().checked_sub(1) else { return s.is_empty(); };
+    let truncated = truncate_str(s, max_len);
+    for (dst, src) in dst.iter_mut().zip(truncated.as_bytes()) {
+        *dst = *src as _;
+    }
+    dst[truncated.len()] = 0;
+    truncated.len() == s.len()
+}
+
+#[test]
+fn test_copy_to_c_buffer() {
+    let mut buf = [1 as std::ffi::c_char; 4];
+    assert!(!copy_to_c_buffer(&mut buf, "aäb"));
+    assert_eq!(buf.map(|c| c as u8), [b'a', 0xc3, 0xa4, 0]);
+    assert!(copy_to_c_buffer(&mut buf, "ab"));
+    assert_eq!(&buf.map(|c| c as u8)[..3], b"ab\0");
+}
+
 /// This lets us access a thread-local version of the `PicaBasic`
 /// suite. Whenever we generate a new `SPBasic_Suite` from Ae somehow,
 /// we create a PicaBasicSuite::new() from that and use that to initialize
@@ -203,6 +250,12 @@ define_enum! {
         ConstProjectModification = ae_sys::A_Err_CONST_PROJECT_MODIFICATION,
         // Acquire suite failed on a required suite.
         MissingSuite             = ae_sys::A_Err_MISSING_SUITE,
+        // The requested frame isn't in the cache yet and an asynchronous render has been started.
+        NotInCacheOrComputePending = ae_sys::A_Err_NOT_IN_CACHE_OR_COMPUTE_PENDING,
+        // Project failed to load.
+        ProjectLoadFatal         = ae_sys::A_Err_PROJECT_LOAD_FATAL,
+        // Effect failed to apply.
+        EffectApplyFatal         = ae_sys::A_Err_EFFECT_APPLY_FATAL,
         InternalStructDamaged    = ae_sys::PF_Err_INTERNAL_STRUCT_DAMAGED,
         // Out of range, or action not allowed on this index.
         InvalidIndex             = ae_sys::PF_Err_INVALID_INDEX,
@@ -234,6 +287,9 @@ impl From<Error> for &'static str {
             Error::WrongThread              => "Call made from wrong thread.",
             Error::ConstProjectModification => "Project changes must originate in the UI/Main thread.",
             Error::MissingSuite             => "Could no aquire suite.",
+            Error::NotInCacheOrComputePending => "Frame not in cache, compute pending.",
+            Error::ProjectLoadFatal         => "Project failed to load.",
+            Error::EffectApplyFatal         => "Effect failed to apply.",
             Error::InternalStructDamaged    => "Internal struct is damaged.",
             Error::InvalidIndex             => "Out of range, or action not allowed on this index.",
             Error::UnrecogizedParameterType => "Unrecognized parameter type",
@@ -472,7 +528,7 @@ impl Add<Time> for Time {
 
 define_struct! {
     ae_sys::A_LRect,
-    #[derive(Eq)]
+    #[derive(Eq, serde::Serialize, serde::Deserialize)]
     /// Note that this has a different ordering of values than LegacyRect!
     Rect {
         left: i32,
@@ -510,11 +566,11 @@ impl Rect {
         }
     }
 
+    /// Grows this rect to also contain `other`. Empty rects don't contribute, so they can't extend the result towards the origin.
     pub fn union<'a>(&'a mut self, other: &Rect) -> &'a mut Rect {
         if self.is_empty() {
             *self = *other;
-        } else {
-            // if !other.is_empty()
+        } else if !other.is_empty() {
             self.left = min(self.left, other.left);
             self.top = min(self.top, other.top);
             self.right = max(self.right, other.right);
@@ -540,6 +596,14 @@ impl Rect {
     }
 }
 
+#[test]
+fn test_rect_union_ignores_empty() {
+    let rect = Rect { left: 10, top: 20, right: 30, bottom: 40 };
+    assert_eq!(*{ rect }.union(&Rect::empty()), rect);
+    assert_eq!(*Rect::empty().union(&rect), rect);
+    assert_eq!(*{ rect }.union(&Rect { left: 0, top: 25, right: 5, bottom: 50 }), Rect { left: 0, top: 20, right: 30, bottom: 50 });
+}
+
 define_struct! {
     ae_sys::A_FloatPoint,
     FloatPoint {
@@ -1,9 +1,13 @@
 macro_rules! ae_acquire_suite_ptr {
     ($pica:expr, $type:ident, $name:ident, $version:ident) => {{
-        unsafe {
+        let pica: *const after_effects_sys::SPBasicSuite = $pica;
+        if pica.is_null() {
+            log::error!("Suite {} requested before the PICA basic suite was set", stringify!($type));
+            Err($crate::Error::InvalidCallback)
+        } else { unsafe {
             let mut suite_ptr = std::mem::MaybeUninit::<*const after_effects_sys::$type>::uninit();
 
-            let aquire_suite_func = (*($pica)).AcquireSuite.unwrap_or_else(|| unreachable!());
+            let aquire_suite_func = (*pica).AcquireSuite.unwrap_or_else(|| unreachable!());
             match aquire_suite_func(
                 after_effects_sys::$name.as_ptr() as *const i8,
                 after_effects_sys::$version as i32,
@@ -16,17 +20,46 @@ macro_rules! ae_acquire_suite_ptr {
                     Err($crate::Error::MissingSuite)
                 },
             }
-        }
+        } }
+    }};
+    // Tries each version in order, returning the first one the host provides together with its version number.
+    ($pica:expr, $type:ident, $name:ident, [$($version:ident),+]) => {{
+        let pica: *const after_effects_sys::SPBasicSuite = $pica;
+        if pica.is_null() {
+            log::error!("Suite {} requested before the PICA basic suite was set", stringify!($type));
+            Err($crate::Error::InvalidCallback)
+        } else { unsafe {
+            let aquire_suite_func = (*pica).AcquireSuite.unwrap_or_else(|| unreachable!());
+            let mut result = Err($crate::Error::MissingSuite);
+            for version in [$(after_effects_sys::$version as i32),+] {
+                let mut suite_ptr = std::mem::MaybeUninit::<*const after_effects_sys::$type>::uninit();
+                if aquire_suite_func(
+                    after_effects_sys::$name.as_ptr() as *const i8,
+                    version,
+                    suite_ptr.as_mut_ptr() as *mut *const _ as _,
+                ) as u32 == after_effects_sys::kSPNoError {
+                    result = Ok((suite_ptr.assume_init(), version));
+                    break;
+                }
+            }
+            if result.is_err() {
+                log::error!("Suite not found: {} {} {}", stringify!($type), stringify!($name), stringify!($($version),+));
+            }
+            result
+        } }
     }};
 }
 
 macro_rules! ae_release_suite_ptr {
     ($pica:expr, $name:ident, $version:ident) => {{
+        ae_release_suite_ptr!($pica, $name, version = after_effects_sys::$version as i32)
+    }};
+    ($pica:expr, $name:ident, version = $version:expr) => {{
         unsafe {
             let release_suite_func = (*($pica)).ReleaseSuite.unwrap_or_else(|| unreachable!());
             release_suite_func(
                 after_effects_sys::$name.as_ptr() as *const i8,
-                after_effects_sys::$version as i32,
+                $version,
             );
         }
     }};
@@ -341,10 +374,9 @@ macro_rules! define_param_wrapper {
     (impl ShortString, $name:ident) => {
         paste::item! {
             pub fn [<set_ $name>](&mut self, v: &str) -> &mut Self {
-                assert!(v.len() < 32);
-                let cstr = CString::new(v).unwrap();
-                let slice = cstr.to_bytes_with_nul();
-                self.def.$name[0..slice.len()].copy_from_slice(unsafe { std::mem::transmute(slice) });
+                if !$crate::copy_to_c_buffer(&mut self.def.$name, v) {
+                    log::warn!("{:?} is too long for {} and was truncated", v, stringify!($name));
+                }
                 self
             }
         }
@@ -479,6 +511,19 @@ macro_rules! define_enum {
                 }
             }
         }
+        impl $name {
+            /// Converts a raw value like `From` does, but returns `None` instead of panicking on values this crate doesn't know,
+            /// e.g. ones added in a newer SDK.
+            #[allow(unreachable_patterns)]
+            pub fn try_from_raw(v: $raw_type) -> Option<Self> {
+                match v as _ {
+                    $(
+                        $value => Some(Self::$variant),
+                    )*
+                    _ => None,
+                }
+            }
+        }
     };
 }
 
@@ -526,6 +571,60 @@ macro_rules! define_suite {
             }
         }
     };
+
+    // Acquires the first available of `$suite_version` and the `fallback` versions.
+    // `$suite_name` must be the newest struct, and the older versions must be prefixes of it,
+    // so functions added in newer versions may only be called after checking `acquired_version()`.
+    ($(#[$attr:meta])* $suite_pretty_name:ident, $suite_name:ident, $suite_name_string:ident, $suite_version:ident, fallback: [$($fallback_version:ident),+]) => {
+        #[derive(Debug, Hash)]
+        $(#[$attr])*
+        pub struct $suite_pretty_name {
+            pica_basic_suite_ptr: *const after_effects_sys::SPBasicSuite,
+            suite_ptr: *const after_effects_sys::$suite_name,
+            suite_version: i32,
+        }
+
+        impl Suite for $suite_pretty_name {
+            fn new() -> Result<Self, Error> {
+                let pica_basic_suite_ptr = borrow_pica_basic_as_ptr();
+
+                let (suite_ptr, suite_version) = ae_acquire_suite_ptr!(
+                    pica_basic_suite_ptr,
+                    $suite_name,
+                    $suite_name_string,
+                    [$suite_version, $($fallback_version),+]
+                )?;
+                Ok(Self {
+                    pica_basic_suite_ptr,
+                    suite_ptr,
+                    suite_version,
+                })
+            }
+        }
+
+        impl $suite_pretty_name {
+            /// The version of the suite that was acquired from the host.
+            pub fn acquired_version(&self) -> i32 {
+                self.suite_version
+            }
+        }
+
+        impl Clone for $suite_pretty_name {
+            fn clone(&self) -> Self {
+                Suite::new().unwrap()
+            }
+        }
+
+        impl Drop for $suite_pretty_name {
+            fn drop(&mut self) {
+                ae_release_suite_ptr!(
+                    self.pica_basic_suite_ptr,
+                    $suite_name_string,
+                    version = self.suite_version
+                );
+            }
+        }
+    };
 }
 
 macro_rules! define_suite_item_wrapper {
@@ -142,6 +142,9 @@ define_suite_item_wrapper!(
         /// Retrieves the read-only const sequence_data object for a rendering thread when Multi-Frame Rendering is enabled for an effect.
         const_sequence_data() -> ae_sys::PF_ConstHandle => effect_sequence_data.const_sequence_data,
 
+        /// Retrieves the const sequence_data copied out as bytes.
+        const_sequence_data_bytes() -> Vec<u8> => effect_sequence_data.const_sequence_data_bytes,
+
         // ―――――――――――――――――――――――――――― Param utils suite functions ――――――――――――――――――――――――――――
 
         /// Force After Effects to refresh the parameter's UI, in the effect controls palette.
@@ -16,7 +16,7 @@ bitflags! {
         const ALWAYS_UPDATE = ae_sys::PF_EO_ALWAYS_UPDATE as ae_sys::A_long;
         // Do not rerender the comp.
         const NEVER_UPDATE  = ae_sys::PF_EO_NEVER_UPDATE  as ae_sys::A_long;
-        // Update the view immediately after the event returns when using pf::InvalidateRect.
+        // Update the view immediately after the event returns when using `EventExtra::invalidate_rect()`.
         const UPDATE_NOW    = ae_sys::PF_EO_UPDATE_NOW    as ae_sys::A_long;
     }
 }
@@ -65,6 +65,7 @@ impl std::fmt::Debug for AdjustCursorEventInfo {
 
 #[derive(Clone, Copy, Debug)]
 pub enum Event {
+    /// Also returned by [`EventExtra::event`] for event types this crate doesn't know.
     None,
     NewContext,
     Activate,
@@ -147,7 +148,8 @@ impl EventExtra {
                 Event::Keydown(KeyDownEventInfo::from_raw(unsafe { &self.as_ref().u.key_down as *const _ as *mut _ }))
             }
             ae_sys::PF_Event_MOUSE_EXITED => Event::MouseExited,
-            _ => unreachable!(),
+            // Event types added in newer hosts than this crate knows about.
+            _ => Event::None,
         }
     }
 
@@ -159,10 +161,33 @@ impl EventExtra {
         unsafe { **self.as_ref().contextH }.w_type.into()
     }
 
+    /// Replaces the out flags, including any set by the host or earlier handling. Prefer [`set_event_out_flag()`](Self::set_event_out_flag).
     pub fn set_event_out_flags(&mut self, flags: EventOutFlags) {
         self.as_mut().evt_out_flags = flags.bits() as _;
     }
 
+    /// Adds `flag` to the out flags, keeping the ones already set.
+    pub fn set_event_out_flag(&mut self, flag: EventOutFlags) {
+        self.as_mut().evt_out_flags |= flag.bits() as ae_sys::PF_EventOutFlags;
+    }
+
+    /// Removes `flag` from the out flags, keeping the others.
+    pub fn clear_event_out_flag(&mut self, flag: EventOutFlags) {
+        self.as_mut().evt_out_flags &= !(flag.bits() as ae_sys::PF_EventOutFlags);
+    }
+
+    pub fn event_out_flags(&self) -> EventOutFlags {
+        EventOutFlags::from_bits_retain(self.as_ref().evt_out_flags as _)
+    }
+
+    /// Invalidates `rect` of the current context, or the whole context if `rect` is `None`, and sets
+    /// [`EventOutFlags::UPDATE_NOW`] so the window is redrawn as soon as the event returns.
+    pub fn invalidate_rect(&mut self, rect: Option<Rect>) -> Result<(), Error> {
+        suites::App::new()?.invalidate_rect(self.context_handle(), rect)?;
+        self.set_event_out_flag(EventOutFlags::UPDATE_NOW);
+        Ok(())
+    }
+
     pub fn param_index(&self) -> usize {
         self.as_ref().effect_win.index as _
     }
@@ -359,3 +384,69 @@ impl<'a> EventCallbacks<'a> {
         }
     }
 }
+
+/// Converts points between the layer's coordinate space and the composition frame for on-canvas drawing.
+///
+/// The transform is read once from the event context, so build a new one for every event as the layer may move between them.
+#[derive(Debug, Copy, Clone)]
+pub struct CoordTransform {
+    layer_to_frame: Matrix3,
+    frame_to_layer: Matrix3,
+}
+
+impl CoordTransform {
+    /// Reads the layer-to-composition transform of the event context at the given time.
+    pub fn new(extra: &EventExtra, curr_time: i32, time_scale: u32) -> Result<Self, Error> {
+        let callbacks = extra.callbacks();
+        let layer_to_frame = callbacks.layer2comp_xform(curr_time, time_scale)?;
+        let frame_to_layer = match callbacks.comp2layer_xform(curr_time, time_scale)? {
+            Some(m) => m,
+            None => Self::invert(&layer_to_frame).ok_or(Error::InvalidParms)?,
+        };
+        Ok(Self { layer_to_frame, frame_to_layer })
+    }
+
+    /// Builds a transform from a row-based layer-to-frame matrix.
+    pub fn from_matrix(layer_to_frame: Matrix3) -> Result<Self, Error> {
+        let frame_to_layer = Self::invert(&layer_to_frame).ok_or(Error::InvalidParms)?;
+        Ok(Self { layer_to_frame, frame_to_layer })
+    }
+
+    pub fn layer_to_frame(&self, pt: FloatPoint) -> FloatPoint {
+        Self::apply(&self.layer_to_frame, pt)
+    }
+
+    pub fn frame_to_layer(&self, pt: FloatPoint) -> FloatPoint {
+        Self::apply(&self.frame_to_layer, pt)
+    }
+
+    fn apply(m: &Matrix3, pt: FloatPoint) -> FloatPoint {
+        // Ae matrices are row-based: [x y 1] * M
+        let m = &m.0;
+        let x = pt.x * m[0][0] + pt.y * m[1][0] + m[2][0];
+        let y = pt.x * m[0][1] + pt.y * m[1][1] + m[2][1];
+        let w = pt.x * m[0][2] + pt.y * m[1][2] + m[2][2];
+        if w != 0.0 && w != 1.0 {
+            FloatPoint { x: x / w, y: y / w }
+        } else {
+            FloatPoint { x, y }
+        }
+    }
+
+    fn invert(m: &Matrix3) -> Option<Matrix3> {
+        let m = &m.0;
+        let cofactor = |r0: usize, r1: usize, c0: usize, c1: usize| m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0];
+        let det = m[0][0] * cofactor(1, 2, 1, 2)
+                - m[0][1] * cofactor(1, 2, 0, 2)
+                + m[0][2] * cofactor(1, 2, 0, 1);
+        if det.abs() < f64::EPSILON {
+            return None;
+        }
+        let inv_det = 1.0 / det;
+        Some(Matrix3([
+            [ cofactor(1, 2, 1, 2) * inv_det, -cofactor(0, 2, 1, 2) * inv_det,  cofactor(0, 1, 1, 2) * inv_det],
+            [-cofactor(1, 2, 0, 2) * inv_det,  cofactor(0, 2, 0, 2) * inv_det, -cofactor(0, 1, 0, 2) * inv_det],
+            [ cofactor(1, 2, 0, 1) * inv_det, -cofactor(0, 2, 0, 1) * inv_det,  cofactor(0, 1, 0, 1) * inv_det],
+        ]))
+    }
+}
@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A small least-recently-used cache for memoizing expensive per-frame computations, e.g. lookup tables derived from the params.
+///
+/// The key is whatever identifies the result, typically the current time and/or the param values that went into it.
+/// Once `capacity` entries are stored, inserting a new one evicts the entry that was used longest ago, so the cache
+/// doesn't grow without bounds during a RAM preview.
+///
+/// The cache is meant to live in the sequence data. It can't be flattened, so mark the field with `#[serde(skip)]` and expect
+/// it to be empty again after the project is saved or the sequence data is copied. With
+/// [`OutFlags2::SupportsThreadedRendering`](crate::OutFlags2::SupportsThreadedRendering) the sequence data is read-only
+/// during render, so updating the cache from render also needs
+/// [`OutFlags2::MutableRenderSequenceDataSlower`](crate::OutFlags2::MutableRenderSequenceDataSlower), which makes the
+/// host serialize the renders of the effect.
+#[derive(Debug, Clone)]
+pub struct FrameCache<K, V> {
+    capacity: usize,
+    tick: u64,
+    entries: HashMap<K, (V, u64)>,
+}
+
+impl<K: Eq + Hash + Clone, V> FrameCache<K, V> {
+    /// Creates an empty cache holding at most `capacity` entries. A capacity of zero is treated as one.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            tick: 0,
+            entries: HashMap::with_capacity(capacity.max(1)),
+        }
+    }
+
+    /// Returns the value for `key`, marking it as recently used.
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        let tick = self.next_tick();
+        self.entries.get_mut(key).map(|(value, used)| {
+            *used = tick;
+            &*value
+        })
+    }
+
+    /// Returns the value for `key`, computing and storing it with `f` if it isn't cached.
+    pub fn get_or_insert_with(&mut self, key: K, f: impl FnOnce() -> V) -> &V {
+        if !self.entries.contains_key(&key) {
+            self.insert(key.clone(), f());
+        }
+        self.get(&key).unwrap()
+    }
+
+    /// Stores `value` for `key`, evicting the least recently used entry if the cache is full.
+    pub fn insert(&mut self, key: K, value: V) {
+        if !self.entries.contains_key(&key) && self.entries.len() >= self.capacity {
+            if let Some(oldest) = self.entries.iter().min_by_key(|(_, (_, used))| *used).map(|(k, _)| k.clone()) {
+                self.entries.remove(&oldest);
+            }
+        }
+        let tick = self.next_tick();
+        self.entries.insert(key, (value, tick));
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Removes all entries, e.g. when a param changed that all cached values depend on.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut cache = FrameCache::new(2);
+        cache.insert(1, "a");
+        cache.insert(2, "b");
+        assert_eq!(cache.get(&1), Some(&"a"));
+        cache.insert(3, "c");
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&2), None);
+        assert_eq!(cache.get_or_insert_with(1, || "x"), &"a");
+        assert_eq!(cache.get_or_insert_with(4, || "d"), &"d");
+        assert_eq!(cache.get(&3), None);
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::VecDeque;
+use crate::*;
+
+/// Keeps copies of the last few rendered frames, for temporal effects like echo, trails or motion blur.
+///
+/// Each frame is stored without row padding in a [`FlatHandle`], so the memory is allocated by the host and counts
+/// towards its memory budget. Once `capacity` frames are stored, pushing a new one evicts the oldest.
+///
+/// It lives in the sequence data like [`FrameCache`], see there for how to store it. Frames are usually pushed during
+/// render, so an effect with threaded rendering has to set [`OutFlags2::MutableRenderSequenceDataSlower`] to use it.
+#[derive(Debug)]
+pub struct FrameRingBuffer {
+    frames: Ring<StoredFrame>,
+}
+
+#[derive(Debug)]
+struct StoredFrame {
+    width: usize,
+    height: usize,
+    world_type: aegp::WorldType,
+    data: FlatHandle<'static>,
+}
+
+impl FrameRingBuffer {
+    /// Creates an empty buffer keeping the last `capacity` frames, and at least the last one.
+    pub fn new(capacity: usize) -> Self {
+        Self { frames: Ring::new(capacity) }
+    }
+
+    /// Copies the pixels of `world` into a new handle as the most recent frame, evicting the oldest frame if the buffer is full.
+    ///
+    /// Returns [`Error::InvalidParms`] for an empty world.
+    pub fn push(&mut self, world: &Layer) -> Result<(), Error> {
+        if world.width() == 0 || world.height() == 0 {
+            return Err(Error::InvalidParms);
+        }
+        let row_len = world.row(0).len();
+        let data = FlatHandle::new_with(row_len * world.height(), |buf| {
+            pack_rows(world, buf);
+            Ok(())
+        })?;
+        self.frames.push(StoredFrame {
+            width: world.width(),
+            height: world.height(),
+            world_type: world.world_type(),
+            data,
+        });
+        Ok(())
+    }
+
+    /// Returns a copy of the frame pushed `frames_ago` pushes before the last one, so 0 is the most recent frame.
+    ///
+    /// The copy is a [`Layer::new_in_memory()`] world with the size and bit depth `push()` was called with. Returns `None`
+    /// if there aren't enough frames stored or the copy couldn't be allocated.
+    pub fn get(&self, frames_ago: usize) -> Option<Layer> {
+        let frame = self.frames.get(frames_ago)?;
+        let ptr = frame.data.as_ptr();
+        let size = frame.data.size().ok()?;
+        if ptr.is_null() {
+            return None;
+        }
+        let bytes = unsafe { std::slice::from_raw_parts(ptr, size) };
+        let mut layer = Layer::new_in_memory(frame.width, frame.height, frame.world_type).ok()?;
+        unpack_rows(bytes, &mut layer).then_some(layer)
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.items.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.frames.items.is_empty()
+    }
+    pub fn capacity(&self) -> usize {
+        self.frames.capacity
+    }
+
+    /// Changes the number of frames to keep, dropping the oldest ones if there are more.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.frames.set_capacity(capacity);
+    }
+
+    /// Disposes all frames, e.g. when the time jumped or a param changed and the history is no longer valid.
+    pub fn clear(&mut self) {
+        self.frames.items.clear();
+    }
+}
+
+/// Bounded queue with index 0 being the newest item, the eviction logic of [`FrameRingBuffer`].
+#[derive(Debug)]
+struct Ring<T> {
+    capacity: usize,
+    items: VecDeque<T>,
+}
+
+impl<T> Ring<T> {
+    fn new(capacity: usize) -> Self {
+        Self { capacity: capacity.max(1), items: VecDeque::with_capacity(capacity.max(1)) }
+    }
+
+    fn push(&mut self, item: T) {
+        if self.items.len() >= self.capacity {
+            self.items.pop_back();
+        }
+        self.items.push_front(item);
+    }
+
+    fn get(&self, ago: usize) -> Option<&T> {
+        self.items.get(ago)
+    }
+
+    fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        self.items.truncate(self.capacity);
+    }
+}
+
+fn pack_rows(world: &Layer, buf: &mut [u8]) {
+    let row_len = world.row(0).len();
+    for (y, dst) in buf.chunks_exact_mut(row_len).enumerate() {
+        dst.copy_from_slice(world.row(y));
+    }
+}
+
+/// Returns `false` if `bytes` doesn't have the size of the pixels of `layer`.
+fn unpack_rows(bytes: &[u8], layer: &mut Layer) -> bool {
+    let row_len = layer.row(0).len();
+    if bytes.len() != row_len * layer.height() {
+        return false;
+    }
+    for (y, src) in bytes.chunks_exact(row_len).enumerate() {
+        layer.row_mut(y).copy_from_slice(src);
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_round_trip_without_padding() {
+        let white = PixelF32 { alpha: 1.0, red: 1.0, green: 1.0, blue: 1.0 };
+        let black = PixelF32 { alpha: 1.0, red: 0.0, green: 0.0, blue: 0.0 };
+        let world = Layer::checkerboard(3, 4, 1, white, black, aegp::WorldType::U15).unwrap();
+
+        let mut buf = vec![0; 3 * 8 * 4];
+        pack_rows(&world, &mut buf);
+        let mut restored = Layer::new_in_memory(3, 4, aegp::WorldType::U15).unwrap();
+        assert!(unpack_rows(&buf, &mut restored));
+        assert!(restored.is_identical_to(&world));
+        assert!(!unpack_rows(&buf[8..], &mut restored));
+    }
+
+    #[test]
+    fn ring_evicts_oldest_and_indexes_from_newest() {
+        let mut ring = Ring::new(3);
+        for frame in 1..=4 {
+            ring.push(frame);
+        }
+        assert_eq!([ring.get(0), ring.get(1), ring.get(2), ring.get(3)], [Some(&4), Some(&3), Some(&2), None]);
+
+        ring.set_capacity(0);
+        assert_eq!(ring.capacity, 1);
+        assert_eq!(ring.items, [4]);
+        ring.push(5);
+        assert_eq!(ring.items, [5]);
+    }
+}
@@ -11,6 +11,35 @@ define_enum! {
     }
 }
 
+/// Information about a GPU device, as returned by [`GPUDeviceSuite::device_info()`](suites::GPUDevice::device_info).
+///
+/// The pointers are the native handles of the framework, e.g. `cl_device_id`, `MTLDevice` or `CUdevice`.
+#[derive(Clone, Copy, Debug)]
+pub struct GpuDeviceInfo {
+    pub framework: GpuFramework,
+    pub compatible: bool,
+    pub platform: *mut std::ffi::c_void,
+    pub device: *mut std::ffi::c_void,
+    pub context: *mut std::ffi::c_void,
+    pub command_queue: *mut std::ffi::c_void,
+    pub offscreen_opengl_context: *mut std::ffi::c_void,
+    pub offscreen_opengl_device: *mut std::ffi::c_void,
+}
+impl From<ae_sys::PF_GPUDeviceInfo> for GpuDeviceInfo {
+    fn from(info: ae_sys::PF_GPUDeviceInfo) -> Self {
+        Self {
+            framework:                info.device_framework.into(),
+            compatible:               info.compatibleB != 0,
+            platform:                 info.platformPV,
+            device:                   info.devicePV,
+            context:                  info.contextPV,
+            command_queue:            info.command_queuePV,
+            offscreen_opengl_context: info.offscreen_opengl_contextPV,
+            offscreen_opengl_device:  info.offscreen_opengl_devicePV,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct GpuDeviceSetupExtra {
     pub(crate) ptr: *mut ae_sys::PF_GPUDeviceSetupExtra,
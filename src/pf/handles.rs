@@ -149,8 +149,9 @@ impl<'a, T: 'a> Handle<'a, T> {
         }
     }
 
-    pub fn size(&self) -> usize {
-        self.suite.handle_size(self.handle) as usize
+    /// Returns the size of the handle in bytes, or an error if the handle is null or the host reports a size that can't be valid.
+    pub fn size(&self) -> Result<usize, Error> {
+        self.suite.checked_handle_size(self.handle)
     }
 
     /*
@@ -262,6 +263,44 @@ impl<'a> FlatHandle<'a> {
         })
     }
 
+    /// Allocates a handle of `size` bytes and lets `fill` write the data directly into it, without going through a [`Vec<u8>`] first.
+    ///
+    /// The buffer is zeroed before `fill` is called. If `fill` returns an error, the handle is disposed and the error is returned.
+    ///
+    /// ```ignore
+    /// let handle = FlatHandle::new_with(pixels.len() * 4, |buf| {
+    ///     for (dst, px) in buf.chunks_exact_mut(4).zip(&pixels) {
+    ///         dst.copy_from_slice(&px.to_le_bytes());
+    ///     }
+    ///     Ok(())
+    /// })?;
+    /// ```
+    pub fn new_with(size: usize, fill: impl FnOnce(&mut [u8]) -> Result<(), Error>) -> Result<FlatHandle<'a>, Error> {
+        let suite = pf::suites::Handle::new()?;
+
+        let handle = suite.new_handle(size as u64);
+        if handle.is_null() {
+            return Err(Error::OutOfMemory);
+        }
+        let this = Self {
+            suite,
+            handle,
+            is_owned: true,
+            _marker: PhantomData,
+        };
+
+        let ptr = this.suite.lock_handle(handle) as *mut u8;
+        if ptr.is_null() {
+            return Err(Error::OutOfMemory);
+        }
+        let result = unsafe {
+            ptr.write_bytes(0, size);
+            fill(std::slice::from_raw_parts_mut(ptr, size))
+        };
+        this.suite.unlock_handle(handle);
+        result.map(|_| this)
+    }
+
     #[inline]
     pub fn resize(&mut self, size: usize) -> Result<(), Error> {
         self.suite.resize_handle(size, &mut self.handle)
@@ -285,7 +324,7 @@ impl<'a> FlatHandle<'a> {
         if ptr.is_null() {
             None
         } else {
-            Some(unsafe { &*std::ptr::slice_from_raw_parts(ptr, self.size()) })
+            Some(unsafe { &*std::ptr::slice_from_raw_parts(ptr, self.size().ok()?) })
         }
     }
 
@@ -295,7 +334,7 @@ impl<'a> FlatHandle<'a> {
         if ptr.is_null() {
             None
         } else {
-            Some(unsafe { &mut *std::ptr::slice_from_raw_parts_mut(ptr, self.size()) })
+            Some(unsafe { &mut *std::ptr::slice_from_raw_parts_mut(ptr, self.size().ok()?) })
         }
     }
 
@@ -312,19 +351,16 @@ impl<'a> FlatHandle<'a> {
     #[inline]
     pub fn to_vec(&self) -> Vec<u8> {
         let ptr = unsafe { *(self.handle as *const *const u8) };
-        if ptr.is_null() {
-            Vec::new()
-        } else {
-            unsafe {
-                &*std::ptr::slice_from_raw_parts(*(self.handle as *const *const u8), self.size())
-            }
-            .to_vec()
+        match self.size() {
+            Ok(size) if !ptr.is_null() => unsafe { &*std::ptr::slice_from_raw_parts(ptr, size) }.to_vec(),
+            _ => Vec::new(),
         }
     }
 
+    /// Returns the size of the handle in bytes, or an error if the handle is null or the host reports a size that can't be valid.
     #[inline]
-    pub fn size(&self) -> usize {
-        self.suite.handle_size(self.handle) as usize
+    pub fn size(&self) -> Result<usize, Error> {
+        self.suite.checked_handle_size(self.handle)
     }
 
     #[inline]
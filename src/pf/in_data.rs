@@ -42,6 +42,15 @@ use std::any::Any;
 //     pub shutter_phase: PF_Fixed,              // Offset from frame time to shutter open time as a percentage of a frame duration.
 // }
 
+/// The application the plugin is running in, as reported by `PF_InData::appl_id`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Host {
+    AfterEffects,
+    Premiere,
+    /// Any other host, with its four character creator code.
+    Other([u8; 4]),
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct InData {
     pub(crate) ptr: *const ae_sys::PF_InData,
@@ -54,8 +63,9 @@ impl AsRef<ae_sys::PF_InData> for InData {
 }
 
 impl InData {
+    /// Wraps the host's `PF_InData`. Panics if `ptr` is null, so the accessors never need to check it again.
     pub fn from_raw(ptr: *const ae_sys::PF_InData) -> Self {
-        assert!(!ptr.is_null());
+        assert!(!ptr.is_null(), "InData::from_raw() called with a null PF_InData pointer");
         Self { ptr }
     }
 
@@ -76,6 +86,14 @@ impl InData {
         unsafe {  (*self.ptr).appl_id == i32::from_be_bytes(*b"FXTC") }
     }
 
+    pub fn host(&self) -> Host {
+        match &self.application_id() {
+            b"FXTC" => Host::AfterEffects,
+            b"PrMr" => Host::Premiere,
+            id      => Host::Other(*id),
+        }
+    }
+
     pub fn quality(&self) -> Quality {
         unsafe { (*self.ptr).quality.into() }
     }
@@ -92,6 +110,7 @@ impl InData {
         Effect::from_raw(unsafe { (*self.ptr).effect_ref })
     }
 
+    /// Returns the `effect_ref` to pass to the suite functions, instead of dereferencing [`as_ptr()`](Self::as_ptr) by hand.
     pub fn effect_ref(&self) -> EffectHandle {
         EffectHandle::from_raw(unsafe { (*self.ptr).effect_ref })
     }
@@ -115,6 +134,12 @@ impl InData {
     pub fn current_timestamp(&self) -> f32 {
         unsafe { (*self.ptr).current_time as f32 / (*self.ptr).time_scale as f32 }
     }
+    /// Formats the current time as `HH:MM:SS:FF` timecode at the frame rate given by `time_scale / time_step`.
+    ///
+    /// 29.97 and 59.94 fps use drop-frame timecode, which is written with a `;` before the frames.
+    pub fn timecode(&self) -> String {
+        unsafe { timecode((*self.ptr).current_time, (*self.ptr).time_step, (*self.ptr).time_scale) }
+    }
     pub fn current_time(&self) -> i32 {
         unsafe { (*self.ptr).current_time }
     }
@@ -151,6 +176,35 @@ impl InData {
         unsafe { (*self.ptr).downsample_y.into() }
     }
 
+    /// Returns the `(x, y)` downsample factors. Scale pixel distances from params by these, e.g. a 4 pixel blur at 1/2 is a 2 pixel blur.
+    ///
+    /// Valid only during [`Command::SequenceSetup`], [`Command::SequenceResetup`], [`Command::FrameSetup`] and [`Command::Render`].
+    pub fn downsample(&self) -> (RationalScale, RationalScale) {
+        (self.downsample_x(), self.downsample_y())
+    }
+
+    /// Motion blur shutter angle in degrees, 0 to 360.
+    ///
+    /// Zero unless motion blur is enabled for the layer. Only valid if [`OutFlags::IUseShutterAngle`] was set during [`Command::GlobalSetup`].
+    pub fn shutter_angle(&self) -> f64 {
+        Fixed::from_fixed(unsafe { (*self.ptr).shutter_angle }).as_f64() * 360.0
+    }
+
+    /// Offset from the frame time to the time the shutter opens, in degrees of a frame duration.
+    ///
+    /// The shutter is open from `current_time + shutter_phase / 360 * time_step` for `shutter_angle / 360 * time_step`.
+    pub fn shutter_phase(&self) -> f64 {
+        Fixed::from_fixed(unsafe { (*self.ptr).shutter_phase }).as_f64() * 360.0
+    }
+
+    /// The number of motion blur samples suggested by the composition of the effect's layer.
+    ///
+    /// This isn't part of `PF_InData`, so it's queried through the AEGP suites and is only available in After Effects.
+    pub fn motion_blur_samples(&self) -> Result<u32, Error> {
+        let samples = self.effect().layer()?.parent_comp()?.suggested_motion_blur_samples()?;
+        Ok(samples.max(0) as u32)
+    }
+
     #[inline]
     pub fn version(&self) -> (i16, i16) {
         unsafe { ((*self.ptr).version.major, (*self.ptr).version.minor) }
@@ -193,6 +247,39 @@ impl InData {
         }
     }
 
+    /// Copies the plain-old-data fields into an owned [`InDataSnapshot`] which can be sent to worker threads.
+    pub fn snapshot(&self) -> InDataSnapshot {
+        let in_data = unsafe { &*self.ptr };
+        InDataSnapshot {
+            width:              in_data.width,
+            height:             in_data.height,
+            current_time:       in_data.current_time,
+            time_step:          in_data.time_step,
+            local_time_step:    in_data.local_time_step,
+            time_scale:         in_data.time_scale,
+            quality:            in_data.quality.into(),
+            field:              in_data.field.into(),
+            extent_hint:        in_data.extent_hint.into(),
+            downsample_x:       in_data.downsample_x.into(),
+            downsample_y:       in_data.downsample_y.into(),
+            pixel_aspect_ratio: in_data.pixel_aspect_ratio.into(),
+            version:            (in_data.version.major, in_data.version.minor),
+        }
+    }
+
+    /// Returns a [`SuiteAcquirer`] which acquires suites from this `PF_InData`'s own PICA basic suite instead of the thread-local one.
+    pub fn suites(&self) -> SuiteAcquirer {
+        SuiteAcquirer { in_data: *self }
+    }
+
+    /// Invalidates `rect` of a custom UI `context`, or the whole context if `rect` is `None`, so it gets redrawn.
+    ///
+    /// The App suite is acquired through [`suites()`](Self::suites), so this also works outside of the entry point, e.g. from a
+    /// timer. Inside an event, prefer [`EventExtra::invalidate_rect`], which also asks for the redraw to happen right away.
+    pub fn invalidate_rect(&self, context: &ContextHandle, rect: Option<Rect>) -> Result<(), Error> {
+        self.suites().with(|| suites::App::new()?.invalidate_rect(context.as_ptr(), rect))
+    }
+
     pub fn interact(&self) -> InteractCallbacks {
         InteractCallbacks::new(*self)
     }
@@ -202,6 +289,94 @@ impl InData {
     }
 }
 
+/// Acquires suites through the `pica_basicP` of a specific [`InData`], see [`InData::suites()`].
+///
+/// The entry points set the thread-local PICA basic suite from the `PF_InData` of every command, so this is only needed
+/// when calling into the crate from somewhere that doesn't, e.g. a callback registered with the host.
+///
+/// ```ignore
+/// let world_suite = in_data.suites().with(pf::suites::World::new)?;
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct SuiteAcquirer {
+    in_data: InData,
+}
+
+impl SuiteAcquirer {
+    /// Runs `f` with the PICA basic suite of the [`InData`] set for the current thread, restoring the previous one afterwards.
+    ///
+    /// Every suite acquired inside `f`, including by the crate itself, comes from the [`InData`]. Suites remember where they were
+    /// acquired from, so they can be kept and released after `f` returns. Returns [`Error::InvalidCallback`] if the `PF_InData` has no
+    /// PICA basic suite.
+    pub fn with<R>(&self, f: impl FnOnce() -> Result<R, Error>) -> Result<R, Error> {
+        if self.in_data.pica_basic_suite_ptr().is_null() {
+            return Err(Error::InvalidCallback);
+        }
+        let _pica = crate::PicaBasicSuite::from_pf_in_data_raw(self.in_data.as_ptr());
+        f()
+    }
+}
+
+/// Owned copy of the [`InData`] fields that don't depend on the host pointer staying valid.
+///
+/// Unlike [`InData`], this is `Send + Sync`. The bit depth isn't part of `PF_InData`, use [`Layer::bit_depth`] instead.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InDataSnapshot {
+    pub width: i32,
+    pub height: i32,
+    pub current_time: i32,
+    pub time_step: i32,
+    pub local_time_step: i32,
+    pub time_scale: u32,
+    pub quality: Quality,
+    pub field: Field,
+    pub extent_hint: Rect,
+    pub downsample_x: RationalScale,
+    pub downsample_y: RationalScale,
+    pub pixel_aspect_ratio: RationalScale,
+    pub version: (i16, i16),
+}
+
+impl InDataSnapshot {
+    pub fn current_frame(&self) -> f32 {
+        self.current_time as f32 / self.time_step as f32
+    }
+    pub fn current_timestamp(&self) -> f32 {
+        self.current_time as f32 / self.time_scale as f32
+    }
+    /// See [`InData::timecode`].
+    pub fn timecode(&self) -> String {
+        timecode(self.current_time, self.time_step, self.time_scale)
+    }
+}
+
+fn timecode(current_time: i32, time_step: i32, time_scale: u32) -> String {
+    if time_step <= 0 || time_scale == 0 {
+        return "00:00:00:00".into();
+    }
+    let fps = time_scale as f64 / time_step as f64;
+    let frame = (current_time as f64 / time_step as f64).floor() as i64;
+    let sign = if frame < 0 { "-" } else { "" };
+    let mut frame = frame.abs();
+
+    let nominal = (fps.round() as i64).max(1);
+    let drop_frame = (nominal == 30 || nominal == 60) && (fps - nominal as f64 * 1000.0 / 1001.0).abs() < 0.01;
+    if drop_frame {
+        // Frame numbers 0 and 1 (0 to 3 at 59.94) are skipped at the start of every minute, except for every tenth minute.
+        let dropped = nominal / 15;
+        let per_10_minutes = nominal * 600 - dropped * 9;
+        let per_minute = nominal * 60 - dropped;
+        let (tens, rest) = (frame / per_10_minutes, frame % per_10_minutes);
+        frame += dropped * 9 * tens;
+        if rest > dropped {
+            frame += dropped * ((rest - dropped) / per_minute);
+        }
+    }
+    let separator = if drop_frame { ';' } else { ':' };
+    let seconds = frame / nominal;
+    format!("{sign}{:02}:{:02}:{:02}{separator}{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60, frame % nominal)
+}
+
 impl AsPtr<*const ae_sys::PF_InData> for *const ae_sys::PF_InData {
     fn as_ptr(&self) -> *const ae_sys::PF_InData {
         *self
@@ -217,3 +392,19 @@ impl AsPtr<*const ae_sys::PF_InData> for &InData {
         self.ptr
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::timecode;
+
+    #[test]
+    fn timecode_handles_drop_frame() {
+        assert_eq!(timecode(90061, 1, 25), "01:00:02:11");
+        assert_eq!(timecode(-50, 2, 50), "-00:00:01:00");
+        assert_eq!(timecode(1799 * 100, 100, 2997), "00:00:59;29");
+        assert_eq!(timecode(1800 * 100, 100, 2997), "00:01:00;02");
+        assert_eq!(timecode(17982 * 100, 100, 2997), "00:10:00;00");
+        assert_eq!(timecode(3600 * 1001, 1001, 60000), "00:01:00;04");
+        assert_eq!(timecode(0, 0, 0), "00:00:00:00");
+    }
+}
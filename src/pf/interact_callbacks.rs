@@ -102,3 +102,92 @@ impl InteractCallbacks {
     // fn checkin_layer_audio(effect_ref: PF_ProgPtr, audio: PF_LayerAudio) -> PF_Err,
     // fn get_audio_data(effect_ref: PF_ProgPtr, audio: PF_LayerAudio, data0: *mut PF_SndSamplePtr, num_samples0: *mut A_long, rate0: *mut PF_UFixed, bytes_per_sample0: *mut A_long, num_channels0: *mut A_long, fmt_signed0: *mut A_long) -> PF_Err,
 }
+
+/// Reports render progress and checks for user interrupts in one call.
+///
+/// Any non-zero return from the host's abort or progress callbacks is surfaced as an error
+/// (usually [`Error::InterruptCancel`]), so long-running loops can simply propagate it with `?`:
+/// ```ignore
+/// let progress = ProgressReporter::new(in_data);
+/// for y in 0..height {
+///     progress.update(y, height)?;
+///     // process row
+/// }
+/// ```
+pub struct ProgressReporter(InteractCallbacks);
+
+impl ProgressReporter {
+    pub fn new(in_data: InData) -> Self {
+        Self(InteractCallbacks::new(in_data))
+    }
+
+    /// Checks the abort callback, then updates the progress bar with `done / total`.
+    pub fn update(&self, done: usize, total: usize) -> Result<(), Error> {
+        self.check_abort()?;
+        self.report(done, total)
+    }
+
+    /// Checks the abort callback without touching the progress bar.
+    pub fn check_abort(&self) -> Result<(), Error> {
+        self.0.abort()
+    }
+
+    /// Turns this into a [`ThrottledProgress`] with the given limits.
+    pub fn throttled(self, min_interval: std::time::Duration, min_step: f32) -> ThrottledProgress {
+        ThrottledProgress {
+            reporter: self,
+            min_interval,
+            min_step,
+            last_update: std::cell::Cell::new(None),
+            last_fraction: std::cell::Cell::new(0.0),
+        }
+    }
+
+    fn report(&self, done: usize, total: usize) -> Result<(), Error> {
+        self.0.progress(done.min(i32::MAX as usize) as i32, total.min(i32::MAX as usize) as i32)
+    }
+}
+
+/// Like [`ProgressReporter`], but only forwards progress to the host when at least `min_interval` passed
+/// or the progress advanced by `min_step` (a fraction between 0 and 1) since the last forwarded update.
+///
+/// Abort is still checked on every call, so it's cheap enough to update once per row or even per pixel.
+/// The final update (`done == total`) is always forwarded.
+pub struct ThrottledProgress {
+    reporter: ProgressReporter,
+    min_interval: std::time::Duration,
+    min_step: f32,
+    last_update: std::cell::Cell<Option<std::time::Instant>>,
+    last_fraction: std::cell::Cell<f32>,
+}
+
+impl ThrottledProgress {
+    pub fn new(in_data: InData, min_interval: std::time::Duration, min_step: f32) -> Self {
+        ProgressReporter::new(in_data).throttled(min_interval, min_step)
+    }
+
+    /// Checks the abort callback, then updates the progress bar with `done / total` if the throttling allows it.
+    pub fn update(&self, done: usize, total: usize) -> Result<(), Error> {
+        self.reporter.check_abort()?;
+
+        let fraction = if total == 0 { 1.0 } else { done as f32 / total as f32 };
+        let now = std::time::Instant::now();
+        let due = match self.last_update.get() {
+            None => true,
+            Some(last) => done >= total
+                || now.duration_since(last) >= self.min_interval
+                || (fraction - self.last_fraction.get()).abs() >= self.min_step,
+        };
+        if due {
+            self.last_update.set(Some(now));
+            self.last_fraction.set(fraction);
+            self.reporter.report(done, total)?;
+        }
+        Ok(())
+    }
+
+    /// Checks the abort callback without touching the progress bar.
+    pub fn check_abort(&self) -> Result<(), Error> {
+        self.reporter.check_abort()
+    }
+}
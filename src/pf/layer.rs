@@ -20,6 +20,30 @@ impl Debug for Layer {
     }
 }
 
+/// Identity and shape of a [`Layer`], see [`Layer::fingerprint()`].
+///
+/// This doesn't hash any pixels: the host reuses buffers, so two fingerprints being equal means the layers have the same
+/// dimensions and point at the same memory, not that the contents are the same. Combine it with the time and param state
+/// if the cache depends on the pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WorldFingerprint {
+    pub width: usize,
+    pub height: usize,
+    pub row_bytes: isize,
+    pub world_type: aegp::WorldType,
+    /// Address of the first pixel.
+    pub data_ptr: usize,
+}
+
+/// Shape of the neighborhood used by [`Layer::erode_with`] and [`Layer::dilate_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StructuringElement {
+    /// All pixels within `radius` along both axes.
+    Square,
+    /// All pixels within a Euclidean distance of `radius`.
+    Circle,
+}
+
 //pub world_flags: PF_WorldFlags,
 //pub data: PF_PixelPtr,
 //pub rowbytes: A_long,
@@ -51,6 +75,47 @@ impl Layer {
         Self { in_data_ptr: in_data.as_ptr(), layer: PointerOwnership::AfterEffects(layer_ptr), drop_fn }
     }
 
+    /// Allocates a zeroed world of the given size and type in Rust memory, freed when the layer is dropped.
+    ///
+    /// The layer isn't known to the host, so it can't be passed to callbacks that expect host-allocated worlds (use
+    /// [`UtilCallbacks::new_world()`] for those), but it's handy for scratch buffers and synthetic input in tests.
+    /// Rows are padded to [`aligned_row_bytes()`](Self::aligned_row_bytes). Returns [`Error::InvalidParms`] for an empty
+    /// size or [`aegp::WorldType::None`].
+    pub fn new_in_memory(width: usize, height: usize, world_type: aegp::WorldType) -> Result<Self, Error> {
+        if width == 0 || height == 0 || world_type == aegp::WorldType::None {
+            return Err(Error::InvalidParms);
+        }
+        let row_bytes = Self::aligned_row_bytes(width, world_type);
+        let layout = Self::in_memory_layout(row_bytes, height)?;
+        let data = unsafe { std::alloc::alloc_zeroed(layout) };
+        if data.is_null() {
+            return Err(Error::OutOfMemory);
+        }
+
+        let mut def: PF_LayerDef = unsafe { std::mem::zeroed() };
+        def.data = data as _;
+        def.width = width as _;
+        def.height = height as _;
+        def.rowbytes = row_bytes as _;
+        def.extent_hint = ae_sys::PF_LRect { left: 0, top: 0, right: width as _, bottom: height as _ };
+        def.world_flags = match world_type {
+            aegp::WorldType::U15 => WorldFlags::DEEP | WorldFlags::WRITEABLE,
+            aegp::WorldType::F32 => WorldFlags::RESERVED1 | WorldFlags::WRITEABLE,
+            _                    => WorldFlags::WRITEABLE,
+        }.bits() as _;
+        def.pix_aspect_ratio = ae_sys::PF_RationalScale { num: 1, den: 1 };
+
+        Ok(Self::from_owned(def, std::ptr::null(), |self_layer| {
+            let layout = Self::in_memory_layout(self_layer.buffer_stride(), self_layer.height()).unwrap();
+            unsafe { std::alloc::dealloc(self_layer.data_ptr_mut(), layout) };
+        }))
+    }
+
+    fn in_memory_layout(row_bytes: usize, height: usize) -> Result<std::alloc::Layout, Error> {
+        let size = row_bytes.checked_mul(height).ok_or(Error::InvalidParms)?;
+        std::alloc::Layout::from_size_align(size, 16).map_err(|_| Error::InvalidParms)
+    }
+
     pub fn width(&self) -> usize {
         self.layer.width as usize
     }
@@ -67,37 +132,49 @@ impl Layer {
         self.layer.extent_hint.into()
     }
 
+    /// Returns the size of the pixel buffer in bytes, `height * |row_bytes|`, or `None` if it overflows `usize` or the height is negative.
+    pub fn checked_data_len(&self) -> Option<usize> {
+        usize::try_from(self.layer.height).ok()?.checked_mul(self.buffer_stride())
+    }
+
+    /// Returns the whole pixel buffer, including row padding.
+    ///
+    /// Returns [`Error::InternalStructDamaged`] if the data pointer is null, the stride is zero or the size overflows.
+    pub fn try_buffer(&self) -> Result<&[u8], Error> {
+        let (ptr, len) = self.buffer_start_and_len()?;
+        Ok(unsafe { std::slice::from_raw_parts(ptr, len) })
+    }
+
+    /// Mutable version of [`try_buffer()`](Self::try_buffer).
+    pub fn try_buffer_mut(&mut self) -> Result<&mut [u8], Error> {
+        let (ptr, len) = self.buffer_start_and_len()?;
+        Ok(unsafe { std::slice::from_raw_parts_mut(ptr as *mut u8, len) })
+    }
+
+    /// Same as [`try_buffer()`](Self::try_buffer), but panics on a damaged layer.
     pub fn buffer(&self) -> &[u8] {
-        // Stride can be negative, so we need to offset the pointer to get to the real beginning of the buffer
-        let offset = if self.row_bytes() < 0 {
-            self.row_bytes() * (self.height() as isize - 1)
-        } else {
-            0
-        };
-        unsafe {
-            assert!(self.row_bytes().abs() > 0);
-            assert!(!self.layer.data.is_null());
-            std::slice::from_raw_parts(
-                (self.layer.data as *const u8).offset(offset),
-                (self.height() as isize * self.row_bytes().abs()) as usize,
-            )
-        }
+        self.try_buffer().expect("Invalid layer buffer")
     }
+    /// Same as [`try_buffer_mut()`](Self::try_buffer_mut), but panics on a damaged layer.
     pub fn buffer_mut(&mut self) -> &mut [u8] {
+        self.try_buffer_mut().expect("Invalid layer buffer")
+    }
+
+    fn buffer_start_and_len(&self) -> Result<(*const u8, usize), Error> {
+        if self.layer.data.is_null() || self.buffer_stride() == 0 {
+            return Err(Error::InternalStructDamaged);
+        }
+        let len = self.checked_data_len().ok_or(Error::InternalStructDamaged)?;
+        if len == 0 {
+            return Ok((self.layer.data as *const u8, 0));
+        }
         // Stride can be negative, so we need to offset the pointer to get to the real beginning of the buffer
         let offset = if self.row_bytes() < 0 {
-            self.row_bytes() * (self.height() as isize - 1)
+            len - self.buffer_stride()
         } else {
             0
         };
-        unsafe {
-            assert!(self.row_bytes().abs() > 0);
-            assert!(!self.layer.data.is_null());
-            std::slice::from_raw_parts_mut(
-                (self.layer.data as *mut u8).offset(offset),
-                (self.height() as isize * self.row_bytes().abs()) as usize,
-            )
-        }
+        Ok((unsafe { (self.layer.data as *const u8).sub(offset) }, len))
     }
 
     pub fn copy_from(&mut self, src: &Self, src_rect: Option<Rect>, dst_rect: Option<Rect>) -> Result<(), Error> {
@@ -183,19 +260,56 @@ impl Layer {
     }
 
     pub fn row_padding_bytes(&self) -> usize {
-        self.buffer_stride()
-            - self.width()
-                * 4
-                * match self.world_type() {
-                    aegp::WorldType::U15 => 2,
-                    aegp::WorldType::U8 => 1,
-                    aegp::WorldType::F32 => 4,
-                    aegp::WorldType::None => panic!(),
-                }
+        self.buffer_stride() - self.width() * Self::bytes_per_pixel(self.world_type())
+    }
+
+    pub(crate) fn bytes_per_pixel(world_type: aegp::WorldType) -> usize {
+        4 * match world_type {
+            aegp::WorldType::U15 => 2,
+            aegp::WorldType::U8 => 1,
+            aegp::WorldType::F32 => 4,
+            aegp::WorldType::None => panic!(),
+        }
+    }
+
+    /// Returns the row stride this crate uses for worlds it allocates itself, see [`new_in_memory()`](Self::new_in_memory).
+    ///
+    /// Rows are padded to a multiple of 16 bytes so that every row start is suitably aligned for SIMD access. This is not
+    /// necessarily what the host uses, always read [`row_bytes()`](Self::row_bytes) of host worlds. Returns 0 for
+    /// [`aegp::WorldType::None`].
+    pub fn aligned_row_bytes(width: usize, world_type: aegp::WorldType) -> usize {
+        const ALIGNMENT: usize = 16;
+        if world_type == aegp::WorldType::None {
+            return 0;
+        }
+        (width * Self::bytes_per_pixel(world_type)).div_ceil(ALIGNMENT) * ALIGNMENT
+    }
+
+    /// Checks that the underlying `PF_LayerDef` is consistent: the data pointer is set,
+    /// the row stride fits a full row of pixels and the world flags describe a single pixel type.
+    ///
+    /// Meant to be used in debug assertions before touching pixel data of worlds that weren't created by the host.
+    pub fn validate(&self) -> Result<(), Error> {
+        let world_type = self.world_type_checked()?;
+        if self.layer.data.is_null() || self.layer.width < 0 || self.layer.height < 0 {
+            return Err(Error::InternalStructDamaged);
+        }
+        if self.buffer_stride() < self.width() * Self::bytes_per_pixel(world_type) {
+            return Err(Error::InternalStructDamaged);
+        }
+        Ok(())
+    }
+
+    /// Panics if `x`, `y` is outside the layer. Only checked in debug builds or with the `runtime-checks` feature.
+    #[inline(always)]
+    fn check_bounds(&self, x: usize, y: usize) {
+        if cfg!(any(debug_assertions, feature = "runtime-checks")) {
+            assert!(x < self.width() && y < self.height(), "Coordinate ({x}, {y}) is outside EffectWorld bounds ({}x{}).", self.width(), self.height());
+        }
     }
 
     pub fn as_pixel8_mut(&self, x: usize, y: usize) -> &mut Pixel8 {
-        debug_assert!(x < self.width() && y < self.height(), "Coordinate is outside EffectWorld bounds.");
+        self.check_bounds(x, y);
         unsafe { &mut *(self.data_ptr_mut().offset(y as isize * self.row_bytes()) as *mut Pixel8).offset(x as isize) }
     }
 
@@ -204,7 +318,7 @@ impl Layer {
     }
 
     pub fn as_pixel16_mut(&self, x: usize, y: usize) -> &mut Pixel16 {
-        debug_assert!(x < self.width() && y < self.height(), "Coordinate is outside EffectWorld bounds.");
+        self.check_bounds(x, y);
         unsafe { &mut *(self.data_ptr_mut().offset(y as isize * self.row_bytes()) as *mut Pixel16).offset(x as isize) }
     }
 
@@ -213,7 +327,7 @@ impl Layer {
     }
 
     pub fn as_pixel32_mut(&self, x: usize, y: usize) -> &mut PixelF32 {
-        debug_assert!(x < self.width() && y < self.height(), "Coordinate is outside EffectWorld bounds.");
+        self.check_bounds(x, y);
         unsafe { &mut *(self.data_ptr_mut().offset(y as isize * self.row_bytes()) as *mut PixelF32).add(x) }
     }
 
@@ -221,6 +335,13 @@ impl Layer {
         self.as_pixel32_mut(x, y)
     }
 
+    /// Returns the pixel type described by the world flags:
+    ///
+    /// * [`WorldFlags::DEEP`] is 16 bits per channel ([`aegp::WorldType::U15`]).
+    /// * [`WorldFlags::RESERVED1`] is 32 bit float ([`aegp::WorldType::F32`]), which is how After Effects marks float worlds.
+    /// * Neither flag is 8 bits per channel ([`aegp::WorldType::U8`]).
+    ///
+    /// Worlds with both flags are reported as 16 bit, use [`world_type_checked()`](Self::world_type_checked) to reject them.
     pub fn world_type(&self) -> aegp::WorldType {
         let flags = WorldFlags::from_bits(self.layer.world_flags as _).unwrap();
         // Most frequent case is 16bit integer.
@@ -233,6 +354,18 @@ impl Layer {
         }
     }
 
+    /// Like [`world_type()`](Self::world_type), but returns [`Error::InternalStructDamaged`] instead of guessing when the flags
+    /// contain unknown bits or both [`WorldFlags::DEEP`] and [`WorldFlags::RESERVED1`].
+    pub fn world_type_checked(&self) -> Result<aegp::WorldType, Error> {
+        let flags = WorldFlags::from_bits(self.layer.world_flags as _).ok_or(Error::InternalStructDamaged)?;
+        match (flags.contains(WorldFlags::DEEP), flags.contains(WorldFlags::RESERVED1)) {
+            (true, true)   => Err(Error::InternalStructDamaged),
+            (true, false)  => Ok(aegp::WorldType::U15),
+            (false, true)  => Ok(aegp::WorldType::F32),
+            (false, false) => Ok(aegp::WorldType::U8),
+        }
+    }
+
     pub fn bit_depth(&self) -> i16 {
         let flags = WorldFlags::from_bits(self.layer.world_flags as _).unwrap();
         if flags.contains(WorldFlags::DEEP) {
@@ -244,6 +377,39 @@ impl Layer {
         }
     }
 
+    /// Returns a cheap key describing the shape and buffer of this layer, for example to decide when to rebuild a derived buffer.
+    pub fn fingerprint(&self) -> WorldFingerprint {
+        WorldFingerprint {
+            width: self.width(),
+            height: self.height(),
+            row_bytes: self.row_bytes(),
+            world_type: self.world_type(),
+            data_ptr: self.layer.data as usize,
+        }
+    }
+
+    /// Returns whether the color channels of this layer are premultiplied with alpha.
+    ///
+    /// After Effects always passes premultiplied layers to effects. In Premiere it depends on the pixel format,
+    /// e.g. `Bgra4444_8u` is straight and `Bgrp4444_8u` is premultiplied.
+    pub fn alpha_mode(&self) -> ModeFlags {
+        if self.in_data_ptr.is_null() || InData::from_raw(self.in_data_ptr).host() != Host::Premiere {
+            return ModeFlags::AlphaPremul;
+        }
+        use pr::PixelFormat::*;
+        match self.pr_pixel_format() {
+            Ok(Bgrp4444_8u | Bgrp4444_16u | Bgrp4444_32f | Bgrp4444_32fLinear |
+               Prgb4444_8u | Prgb4444_16u | Prgb4444_32f | Prgb4444_32fLinear |
+               Vuyp4444_8u | Vuyp4444_8u709 | Vuyp4444_32f | Vuyp4444_32f709) => ModeFlags::AlphaPremul,
+            _ => ModeFlags::AlphaStraight,
+        }
+    }
+
+    /// Shorthand for `alpha_mode() == ModeFlags::AlphaPremul`.
+    pub fn premultiplied(&self) -> bool {
+        self.alpha_mode() == ModeFlags::AlphaPremul
+    }
+
     pub fn pixel_format(&self) -> Result<PixelFormat, Error> {
         pf::suites::World::new()?.pixel_format(self)
     }
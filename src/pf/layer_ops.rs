@@ -0,0 +1,1778 @@
+use super::*;
+
+impl Layer {
+    /// Reads the pixel at `x`, `y` converted to floating point, regardless of the bit depth of the layer.
+    pub fn pixel_f32(&self, x: usize, y: usize) -> PixelF32 {
+        match self.world_type() {
+            aegp::WorldType::U15 => pixel16_to_f32(*self.as_pixel16(x, y)),
+            aegp::WorldType::F32 => *self.as_pixel32(x, y),
+            _                    => pixel8_to_f32(*self.as_pixel8(x, y)),
+        }
+    }
+
+    /// Writes a floating point pixel at `x`, `y`, converting it to the bit depth of the layer.
+    pub fn set_pixel_f32(&mut self, x: usize, y: usize, pixel: PixelF32) {
+        match self.world_type() {
+            aegp::WorldType::U15 => *self.as_pixel16_mut(x, y) = pixel_f32_to_16(pixel),
+            aegp::WorldType::F32 => *self.as_pixel32_mut(x, y) = pixel,
+            _                    => *self.as_pixel8_mut(x, y) = pixel_f32_to_8(pixel),
+        }
+    }
+
+    // ――――――――――――――――――――――――――――――――――――― RGBA bytes ―――――――――――――――――――――――――――――――――――――
+
+    /// Allocates a new 8-bit layer and fills it with `rgba`, tightly packed 8-bit RGBA pixels row by row, e.g. a decoded image resource.
+    ///
+    /// Returns [`Error::InvalidParms`] if `rgba` doesn't hold exactly `width * height` pixels.
+    pub fn from_rgba8(in_data: impl AsPtr<*const ae_sys::PF_InData>, width: usize, height: usize, rgba: &[u8]) -> Result<Layer, Error> {
+        if rgba.len() != width * height * 4 {
+            return Err(Error::InvalidParms);
+        }
+        let mut layer = suites::World::new()?.new_world(in_data, width as i32, height as i32, false, PixelFormat::Argb32)?;
+        layer.write_rgba8(rgba)?;
+        Ok(layer)
+    }
+
+    /// Writes tightly packed 8-bit RGBA pixels into the layer, reordering the channels, skipping the row padding and converting to the bit depth of the layer.
+    ///
+    /// Returns [`Error::InvalidParms`] if `rgba` doesn't hold exactly one pixel per pixel of the layer.
+    pub fn write_rgba8(&mut self, rgba: &[u8]) -> Result<(), Error> {
+        let width = self.width();
+        if rgba.len() != width * self.height() * 4 {
+            return Err(Error::InvalidParms);
+        }
+        for (i, px) in rgba.chunks_exact(4).enumerate() {
+            let (x, y) = (i % width, i / width);
+            let px = Pixel8 { alpha: px[3], red: px[0], green: px[1], blue: px[2] };
+            match self.world_type() {
+                aegp::WorldType::U8 => *self.as_pixel8_mut(x, y) = px,
+                _                   => self.set_pixel_f32(x, y, pixel8_to_f32(px)),
+            }
+        }
+        Ok(())
+    }
+
+    // ―――――――――――――――――――――――――――――――――――― Sampling ――――――――――――――――――――――――――――――――――――
+
+    /// Returns the pixel closest to `x`, `y`. Pixel centers are at integer coordinates.
+    ///
+    /// Coordinates outside the layer are clamped to the nearest edge pixel. An empty layer samples as transparent black.
+    pub fn sample_nearest(&self, x: f32, y: f32) -> PixelF32 {
+        if self.width() == 0 || self.height() == 0 {
+            return PixelF32 { alpha: 0.0, red: 0.0, green: 0.0, blue: 0.0 };
+        }
+        let x = (x.round().max(0.0) as usize).min(self.width() - 1);
+        let y = (y.round().max(0.0) as usize).min(self.height() - 1);
+        self.pixel_f32(x, y)
+    }
+
+    /// Bilinearly interpolates the four pixels around `x`, `y`. Pixel centers are at integer coordinates.
+    ///
+    /// Coordinates outside the layer are clamped to the nearest edge pixel. An empty layer samples as transparent black.
+    pub fn sample_bilinear(&self, x: f32, y: f32) -> PixelF32 {
+        if self.width() == 0 || self.height() == 0 {
+            return PixelF32 { alpha: 0.0, red: 0.0, green: 0.0, blue: 0.0 };
+        }
+        let max_x = (self.width() - 1) as f32;
+        let max_y = (self.height() - 1) as f32;
+        let x = x.clamp(0.0, max_x);
+        let y = y.clamp(0.0, max_y);
+
+        let x0 = x.floor() as usize;
+        let y0 = y.floor() as usize;
+        let x1 = (x0 + 1).min(self.width() - 1);
+        let y1 = (y0 + 1).min(self.height() - 1);
+        let fx = x - x0 as f32;
+        let fy = y - y0 as f32;
+
+        let lerp = |a: PixelF32, b: PixelF32, t: f32| PixelF32 {
+            alpha: a.alpha + (b.alpha - a.alpha) * t,
+            red:   a.red   + (b.red   - a.red)   * t,
+            green: a.green + (b.green - a.green) * t,
+            blue:  a.blue  + (b.blue  - a.blue)  * t,
+        };
+        let top    = lerp(self.pixel_f32(x0, y0), self.pixel_f32(x1, y0), fx);
+        let bottom = lerp(self.pixel_f32(x0, y1), self.pixel_f32(x1, y1), fx);
+        lerp(top, bottom, fy)
+    }
+
+    /// Iterates over the aligned 2x2 blocks of an 8 bit layer, row by row, as `[top left, top right, bottom left, bottom right]`.
+    ///
+    /// With an odd width or height the blocks of the last column or row are clamped to the edge, i.e. they repeat the edge pixels.
+    /// Returns [`Error::InvalidParms`] if the layer isn't 8 bit.
+    pub fn blocks_2x2_pixel8(&self) -> Result<impl Iterator<Item = [&Pixel8; 4]> + '_, Error> {
+        if self.world_type() != aegp::WorldType::U8 {
+            return Err(Error::InvalidParms);
+        }
+        let (width, height) = (self.width(), self.height());
+        Ok((0..height.div_ceil(2)).flat_map(move |by| {
+            (0..width.div_ceil(2)).map(move |bx| {
+                let (x0, y0) = (bx * 2, by * 2);
+                let (x1, y1) = ((x0 + 1).min(width - 1), (y0 + 1).min(height - 1));
+                [self.as_pixel8(x0, y0), self.as_pixel8(x1, y0), self.as_pixel8(x0, y1), self.as_pixel8(x1, y1)]
+            })
+        }))
+    }
+
+    /// Mutable variant of [`blocks_2x2_pixel8()`](Self::blocks_2x2_pixel8).
+    ///
+    /// To keep the returned references disjoint, the positions that would be clamped to the edge are `None` instead.
+    pub fn blocks_2x2_pixel8_mut(&mut self) -> Result<impl Iterator<Item = [Option<&mut Pixel8>; 4]> + '_, Error> {
+        if self.world_type() != aegp::WorldType::U8 {
+            return Err(Error::InvalidParms);
+        }
+        let (width, height) = (self.width(), self.height());
+        let this: &Self = self;
+        Ok((0..height.div_ceil(2)).flat_map(move |by| {
+            (0..width.div_ceil(2)).map(move |bx| {
+                let (x0, y0) = (bx * 2, by * 2);
+                let (has_x1, has_y1) = (x0 + 1 < width, y0 + 1 < height);
+                [
+                    Some(this.as_pixel8_mut(x0, y0)),
+                    has_x1.then(|| this.as_pixel8_mut(x0 + 1, y0)),
+                    has_y1.then(|| this.as_pixel8_mut(x0, y0 + 1)),
+                    (has_x1 && has_y1).then(|| this.as_pixel8_mut(x0 + 1, y0 + 1)),
+                ]
+            })
+        }))
+    }
+
+    // ――――――――――――――――――――――――――――――――――――― Resizing ―――――――――――――――――――――――――――――――――――――
+
+    /// Scales the contents of this layer into `dst`, using the dimensions of `dst`.
+    ///
+    /// When the layer is reduced along any axis it is box filtered, i.e. every destination pixel is the average of the source pixels it covers.
+    /// When it is enlarged along both axes it is bilinearly interpolated, with coordinates clamped to the edge pixels.
+    ///
+    /// Both layers must have the same world type, otherwise [`Error::InvalidParms`] is returned.
+    pub fn resize(&self, dst: &mut Layer) -> Result<(), Error> {
+        if self.world_type() != dst.world_type() {
+            return Err(Error::InvalidParms);
+        }
+        let (src_w, src_h) = (self.width(), self.height());
+        let (dst_w, dst_h) = (dst.width(), dst.height());
+        if src_w == 0 || src_h == 0 || dst_w == 0 || dst_h == 0 {
+            return Ok(());
+        }
+        let scale_x = src_w as f32 / dst_w as f32;
+        let scale_y = src_h as f32 / dst_h as f32;
+
+        for y in 0..dst_h {
+            for x in 0..dst_w {
+                let pixel = if scale_x <= 1.0 && scale_y <= 1.0 {
+                    self.sample_bilinear((x as f32 + 0.5) * scale_x - 0.5, (y as f32 + 0.5) * scale_y - 0.5)
+                } else {
+                    self.box_average(
+                        x as f32 * scale_x, (x + 1) as f32 * scale_x,
+                        y as f32 * scale_y, (y + 1) as f32 * scale_y,
+                    )
+                };
+                dst.set_pixel_f32(x, y, pixel);
+            }
+        }
+        Ok(())
+    }
+
+    /// Fills this layer by repeating `src` in both directions, with the top left corner of one tile at `offset`.
+    ///
+    /// Animating `offset` scrolls the texture. Both layers must have the same world type and `src` must not be empty,
+    /// otherwise [`Error::InvalidParms`] is returned.
+    pub fn tile_from(&mut self, src: &Layer, offset: Point) -> Result<(), Error> {
+        if self.world_type() != src.world_type() || src.width() == 0 || src.height() == 0 {
+            return Err(Error::InvalidParms);
+        }
+        let bpp = Self::bytes_per_pixel(self.world_type());
+        let (src_w, src_h) = (src.width() as i64, src.height() as i64);
+        let start_x = (-(offset.h as i64)).rem_euclid(src_w) as usize;
+
+        for y in 0..self.height() {
+            let sy = (y as i64 - offset.v as i64).rem_euclid(src_h) as usize;
+            let src_row = src.row(sy);
+            let dst_row = self.row_mut(y);
+            // Copy whole spans of the source row, starting part-way into the first one.
+            let mut sx = start_x;
+            let mut x = 0;
+            while x < dst_row.len() {
+                let len = (src_row.len() - sx * bpp).min(dst_row.len() - x);
+                dst_row[x..x + len].copy_from_slice(&src_row[sx * bpp..sx * bpp + len]);
+                x += len;
+                sx = 0;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes this layer at half resolution into `dst`, averaging 2×2 blocks.
+    ///
+    /// `dst` must be half the size, rounded up; with an odd width or height the last column or row averages the pixels it has.
+    /// Both layers must have the same world type and these dimensions, otherwise [`Error::InvalidParms`] is returned.
+    pub fn downsample_2x(&self, dst: &mut Layer) -> Result<(), Error> {
+        if self.world_type() != dst.world_type() || dst.width() != self.width().div_ceil(2) || dst.height() != self.height().div_ceil(2) {
+            return Err(Error::InvalidParms);
+        }
+        for y in 0..dst.height() {
+            for x in 0..dst.width() {
+                let (x0, y0) = ((2 * x) as f32, (2 * y) as f32);
+                dst.set_pixel_f32(x, y, self.box_average(x0, x0 + 2.0, y0, y0 + 2.0));
+            }
+        }
+        Ok(())
+    }
+
+    fn box_average(&self, x0: f32, x1: f32, y0: f32, y1: f32) -> PixelF32 {
+        let x_start = (x0.floor() as usize).min(self.width() - 1);
+        let y_start = (y0.floor() as usize).min(self.height() - 1);
+        let x_end = (x1.ceil() as usize).clamp(x_start + 1, self.width());
+        let y_end = (y1.ceil() as usize).clamp(y_start + 1, self.height());
+
+        let mut sum = PixelF32 { alpha: 0.0, red: 0.0, green: 0.0, blue: 0.0 };
+        for sy in y_start..y_end {
+            for sx in x_start..x_end {
+                let p = self.pixel_f32(sx, sy);
+                sum.alpha += p.alpha;
+                sum.red   += p.red;
+                sum.green += p.green;
+                sum.blue  += p.blue;
+            }
+        }
+        let n = ((x_end - x_start) * (y_end - y_start)) as f32;
+        PixelF32 { alpha: sum.alpha / n, red: sum.red / n, green: sum.green / n, blue: sum.blue / n }
+    }
+
+    // ―――――――――――――――――――――――――――――――――――――― Filtering ―――――――――――――――――――――――――――――――――――――
+
+    /// Convolves this layer with a `kernel_width` × `kernel_height` kernel and writes the result into `dst`.
+    ///
+    /// The kernel is stored row by row and centered on the pixel being computed, so both dimensions must be odd.
+    /// It is applied as-is, so normalize it yourself (i.e. make the weights sum to 1) if the overall brightness should be preserved.
+    /// All channels, including alpha, are filtered in floating point, sampling outside the layer is clamped to the nearest edge pixel,
+    /// and the result is clamped to the range of integer world types; float layers keep values outside 0..1.
+    ///
+    /// `dst` must have the same world type and dimensions as this layer, otherwise [`Error::InvalidParms`] is returned.
+    pub fn convolve(&self, kernel: &[f32], kernel_width: usize, kernel_height: usize, dst: &mut Layer) -> Result<(), Error> {
+        if kernel_width.is_multiple_of(2) || kernel_height.is_multiple_of(2) || kernel.len() != kernel_width * kernel_height {
+            return Err(Error::InvalidParms);
+        }
+        if self.world_type() != dst.world_type() || self.width() != dst.width() || self.height() != dst.height() {
+            return Err(Error::InvalidParms);
+        }
+        let (width, height) = (self.width() as isize, self.height() as isize);
+        let (half_w, half_h) = ((kernel_width / 2) as isize, (kernel_height / 2) as isize);
+
+        for y in 0..height {
+            for x in 0..width {
+                let mut sum = PixelF32 { alpha: 0.0, red: 0.0, green: 0.0, blue: 0.0 };
+                for (i, weight) in kernel.iter().enumerate() {
+                    if *weight == 0.0 {
+                        continue;
+                    }
+                    let sx = (x + (i % kernel_width) as isize - half_w).clamp(0, width - 1);
+                    let sy = (y + (i / kernel_width) as isize - half_h).clamp(0, height - 1);
+                    let p = self.pixel_f32(sx as usize, sy as usize);
+                    sum.alpha += p.alpha * weight;
+                    sum.red   += p.red   * weight;
+                    sum.green += p.green * weight;
+                    sum.blue  += p.blue  * weight;
+                }
+                dst.set_pixel_f32(x as usize, y as usize, sum);
+            }
+        }
+        Ok(())
+    }
+
+    /// Blurs this layer with a Gaussian of standard deviation `sigma` pixels and writes the result into `dst`.
+    ///
+    /// See [`gaussian_blur_xy()`](Self::gaussian_blur_xy) for the details.
+    pub fn gaussian_blur(&self, sigma: f32, dst: &mut Layer) -> Result<(), Error> {
+        self.gaussian_blur_xy(sigma, sigma, dst)
+    }
+
+    /// Blurs this layer with a Gaussian with separate horizontal and vertical standard deviations and writes the result into `dst`.
+    ///
+    /// The blur is done in two separable passes through a float scratch buffer, with normalized kernels reaching out to 3 sigma.
+    /// All channels, including alpha, are blurred, so premultiplied layers give correct edges; sampling outside the layer is clamped
+    /// to the nearest edge pixel. A sigma of 0 leaves that axis unblurred.
+    ///
+    /// `dst` must have the same world type and dimensions as this layer and the sigmas must not be negative,
+    /// otherwise [`Error::InvalidParms`] is returned.
+    pub fn gaussian_blur_xy(&self, sigma_x: f32, sigma_y: f32, dst: &mut Layer) -> Result<(), Error> {
+        if !(sigma_x >= 0.0 && sigma_y >= 0.0) {
+            return Err(Error::InvalidParms);
+        }
+        if self.world_type() != dst.world_type() || self.width() != dst.width() || self.height() != dst.height() {
+            return Err(Error::InvalidParms);
+        }
+        let (width, height) = (self.width(), self.height());
+        let (kernel_x, kernel_y) = (gaussian_kernel(sigma_x), gaussian_kernel(sigma_y));
+        let (radius_x, radius_y) = ((kernel_x.len() / 2) as isize, (kernel_y.len() / 2) as isize);
+        let accumulate = |sum: &mut PixelF32, p: PixelF32, weight: f32| {
+            sum.alpha += p.alpha * weight;
+            sum.red   += p.red   * weight;
+            sum.green += p.green * weight;
+            sum.blue  += p.blue  * weight;
+        };
+
+        let mut scratch = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let mut sum = PixelF32 { alpha: 0.0, red: 0.0, green: 0.0, blue: 0.0 };
+                for (i, weight) in kernel_x.iter().enumerate() {
+                    let sx = (x as isize + i as isize - radius_x).clamp(0, width as isize - 1);
+                    accumulate(&mut sum, self.pixel_f32(sx as usize, y), *weight);
+                }
+                scratch.push(sum);
+            }
+        }
+        for y in 0..height {
+            for x in 0..width {
+                let mut sum = PixelF32 { alpha: 0.0, red: 0.0, green: 0.0, blue: 0.0 };
+                for (i, weight) in kernel_y.iter().enumerate() {
+                    let sy = (y as isize + i as isize - radius_y).clamp(0, height as isize - 1);
+                    accumulate(&mut sum, scratch[sy as usize * width + x], *weight);
+                }
+                dst.set_pixel_f32(x, y, sum);
+            }
+        }
+        Ok(())
+    }
+
+    /// Shrinks the alpha channel by `radius` pixels with a circular neighborhood, writing the result into `dst`.
+    ///
+    /// See [`erode_with()`](Self::erode_with) for the details.
+    pub fn erode(&self, radius: u32, dst: &mut Layer) -> Result<(), Error> {
+        self.erode_with(PixelChannel::Alpha, radius, StructuringElement::Circle, dst)
+    }
+
+    /// Grows the alpha channel by `radius` pixels with a circular neighborhood, writing the result into `dst`.
+    ///
+    /// See [`erode_with()`](Self::erode_with) for the details.
+    pub fn dilate(&self, radius: u32, dst: &mut Layer) -> Result<(), Error> {
+        self.dilate_with(PixelChannel::Alpha, radius, StructuringElement::Circle, dst)
+    }
+
+    /// Replaces `channel` of every pixel with its minimum over the neighborhood given by `shape` and `radius`, writing the result into `dst`.
+    ///
+    /// The other channels are copied, except that premultiplied layers keep their straight color when the alpha changes.
+    /// Sampling outside the layer is clamped to the nearest edge pixel.
+    ///
+    /// `dst` must have the same world type and dimensions as this layer and `channel` can't be [`PixelChannel::Luminance`],
+    /// otherwise [`Error::InvalidParms`] is returned.
+    pub fn erode_with(&self, channel: PixelChannel, radius: u32, shape: StructuringElement, dst: &mut Layer) -> Result<(), Error> {
+        self.morphology(channel, radius, shape, f32::min, dst)
+    }
+
+    /// Like [`erode_with()`](Self::erode_with), but takes the maximum over the neighborhood.
+    pub fn dilate_with(&self, channel: PixelChannel, radius: u32, shape: StructuringElement, dst: &mut Layer) -> Result<(), Error> {
+        self.morphology(channel, radius, shape, f32::max, dst)
+    }
+
+    fn morphology(&self, channel: PixelChannel, radius: u32, shape: StructuringElement, pick: fn(f32, f32) -> f32, dst: &mut Layer) -> Result<(), Error> {
+        if channel == PixelChannel::Luminance {
+            return Err(Error::InvalidParms);
+        }
+        if self.world_type() != dst.world_type() || self.width() != dst.width() || self.height() != dst.height() {
+            return Err(Error::InvalidParms);
+        }
+        let (width, height) = (self.width(), self.height());
+        let radius = radius as isize;
+        let mut values = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                values.push(channel.value(self.pixel_f32(x, y)));
+            }
+        }
+        let value = |x: isize, y: isize| values[y.clamp(0, height as isize - 1) as usize * width + x.clamp(0, width as isize - 1) as usize];
+
+        // The square is separable, so it's done as a horizontal and a vertical pass. The circle is done row by row of the element.
+        let picked = match shape {
+            StructuringElement::Square => {
+                let mut rows = Vec::with_capacity(width * height);
+                for y in 0..height as isize {
+                    for x in 0..width as isize {
+                        rows.push((-radius..=radius).map(|dx| value(x + dx, y)).fold(value(x, y), pick));
+                    }
+                }
+                let row_value = |x: usize, y: isize| rows[y.clamp(0, height as isize - 1) as usize * width + x];
+                (0..width * height).map(|i| {
+                    let (x, y) = (i % width, (i / width) as isize);
+                    (-radius..=radius).map(|dy| row_value(x, y + dy)).fold(row_value(x, y), pick)
+                }).collect::<Vec<_>>()
+            }
+            StructuringElement::Circle => {
+                let spans: Vec<isize> = (-radius..=radius).map(|dy| (((radius * radius - dy * dy) as f64).sqrt()) as isize).collect();
+                (0..width * height).map(|i| {
+                    let (x, y) = ((i % width) as isize, (i / width) as isize);
+                    let mut v = value(x, y);
+                    for (dy, span) in (-radius..=radius).zip(&spans) {
+                        for dx in -span..=*span {
+                            v = pick(v, value(x + dx, y + dy));
+                        }
+                    }
+                    v
+                }).collect::<Vec<_>>()
+            }
+        };
+
+        let premultiplied = self.premultiplied();
+        for y in 0..height {
+            for x in 0..width {
+                let mut p = self.pixel_f32(x, y);
+                let v = picked[y * width + x];
+                match channel {
+                    PixelChannel::Alpha => p = with_alpha(p, v, premultiplied),
+                    PixelChannel::Red   => p.red   = v,
+                    PixelChannel::Green => p.green = v,
+                    PixelChannel::Blue  => p.blue  = v,
+                    PixelChannel::Luminance => unreachable!(),
+                }
+                dst.set_pixel_f32(x, y, p);
+            }
+        }
+        Ok(())
+    }
+
+    // ―――――――――――――――――――――――――――――――――――――― Mapping ――――――――――――――――――――――――――――――――――――――
+
+    /// Writes `f(pixel)` into `dst` for every pixel of this 8-bit layer.
+    ///
+    /// Both layers must be 8-bit and have the same dimensions, otherwise [`Error::InvalidParms`] is returned.
+    pub fn map8(&self, dst: &mut Layer, f: impl Fn(Pixel8) -> Pixel8) -> Result<(), Error> {
+        self.map_into(dst, aegp::WorldType::U8, f)
+    }
+
+    /// Writes `f(pixel)` into `dst` for every pixel of this 16-bit layer.
+    ///
+    /// Both layers must be 16-bit and have the same dimensions, otherwise [`Error::InvalidParms`] is returned.
+    pub fn map16(&self, dst: &mut Layer, f: impl Fn(Pixel16) -> Pixel16) -> Result<(), Error> {
+        self.map_into(dst, aegp::WorldType::U15, f)
+    }
+
+    /// Writes `f(pixel)` into `dst` for every pixel of this float layer.
+    ///
+    /// Both layers must be float and have the same dimensions, otherwise [`Error::InvalidParms`] is returned.
+    pub fn map32(&self, dst: &mut Layer, f: impl Fn(PixelF32) -> PixelF32) -> Result<(), Error> {
+        self.map_into(dst, aegp::WorldType::F32, f)
+    }
+
+    /// Replaces every pixel of this 8-bit layer with `f(pixel)`. Returns [`Error::InvalidParms`] if the layer is not 8-bit.
+    pub fn map8_mut(&mut self, f: impl Fn(Pixel8) -> Pixel8) -> Result<(), Error> {
+        self.map_in_place(aegp::WorldType::U8, f)
+    }
+
+    /// Replaces every pixel of this 16-bit layer with `f(pixel)`. Returns [`Error::InvalidParms`] if the layer is not 16-bit.
+    pub fn map16_mut(&mut self, f: impl Fn(Pixel16) -> Pixel16) -> Result<(), Error> {
+        self.map_in_place(aegp::WorldType::U15, f)
+    }
+
+    /// Replaces every pixel of this float layer with `f(pixel)`. Returns [`Error::InvalidParms`] if the layer is not float.
+    pub fn map32_mut(&mut self, f: impl Fn(PixelF32) -> PixelF32) -> Result<(), Error> {
+        self.map_in_place(aegp::WorldType::F32, f)
+    }
+
+    /// Depth-generic version of [`map8`](Self::map8)/[`map16`](Self::map16)/[`map32`](Self::map32).
+    ///
+    /// ```ignore
+    /// fn invert<P: AePixel>(src: &Layer, dst: &mut Layer) -> Result<(), Error> {
+    ///     src.map(dst, |p: P| {
+    ///         let [a, r, g, b] = p.channels();
+    ///         P::from_channels([a, a - r, a - g, a - b])
+    ///     })
+    /// }
+    /// match src.world_type() {
+    ///     aegp::WorldType::U8  => invert::<Pixel8>(&src, &mut dst)?,
+    ///     aegp::WorldType::U15 => invert::<Pixel16>(&src, &mut dst)?,
+    ///     _                    => invert::<PixelF32>(&src, &mut dst)?,
+    /// }
+    /// ```
+    pub fn map<P: AePixel>(&self, dst: &mut Layer, f: impl Fn(P) -> P) -> Result<(), Error> {
+        self.map_into(dst, P::WORLD_TYPE, f)
+    }
+
+    /// Depth-generic version of [`map8_mut`](Self::map8_mut)/[`map16_mut`](Self::map16_mut)/[`map32_mut`](Self::map32_mut).
+    pub fn map_mut<P: AePixel>(&mut self, f: impl Fn(P) -> P) -> Result<(), Error> {
+        self.map_in_place(P::WORLD_TYPE, f)
+    }
+
+    /// Calls `f` with each row of the 8-bit layer `src` and the matching row of `dst`, both exactly `width` pixels long.
+    ///
+    /// Both layers must be 8-bit and have the same dimensions, otherwise [`Error::InvalidParms`] is returned.
+    pub fn for_each_row_pair8(src: &Layer, dst: &mut Layer, f: impl FnMut(&[Pixel8], &mut [Pixel8])) -> Result<(), Error> {
+        Self::for_each_row_pair(src, dst, aegp::WorldType::U8, f)
+    }
+
+    /// Calls `f` with each row of the 16-bit layer `src` and the matching row of `dst`, both exactly `width` pixels long.
+    ///
+    /// Both layers must be 16-bit and have the same dimensions, otherwise [`Error::InvalidParms`] is returned.
+    pub fn for_each_row_pair16(src: &Layer, dst: &mut Layer, f: impl FnMut(&[Pixel16], &mut [Pixel16])) -> Result<(), Error> {
+        Self::for_each_row_pair(src, dst, aegp::WorldType::U15, f)
+    }
+
+    /// Calls `f` with each row of the float layer `src` and the matching row of `dst`, both exactly `width` pixels long.
+    ///
+    /// Both layers must be float and have the same dimensions, otherwise [`Error::InvalidParms`] is returned.
+    pub fn for_each_row_pair32(src: &Layer, dst: &mut Layer, f: impl FnMut(&[PixelF32], &mut [PixelF32])) -> Result<(), Error> {
+        Self::for_each_row_pair(src, dst, aegp::WorldType::F32, f)
+    }
+
+    fn for_each_row_pair<P>(src: &Layer, dst: &mut Layer, world_type: aegp::WorldType, mut f: impl FnMut(&[P], &mut [P])) -> Result<(), Error> {
+        if src.world_type() != world_type || dst.world_type() != world_type || src.width() != dst.width() || src.height() != dst.height() {
+            return Err(Error::InvalidParms);
+        }
+        let width = src.width();
+        for y in 0..src.height() {
+            let src_row = unsafe { std::slice::from_raw_parts(src.row(y).as_ptr() as *const P, width) };
+            let dst_row = unsafe { std::slice::from_raw_parts_mut(dst.row_mut(y).as_mut_ptr() as *mut P, width) };
+            f(src_row, dst_row);
+        }
+        Ok(())
+    }
+
+    fn map_into<P: Copy>(&self, dst: &mut Layer, world_type: aegp::WorldType, f: impl Fn(P) -> P) -> Result<(), Error> {
+        if self.world_type() != world_type || dst.world_type() != world_type || self.width() != dst.width() || self.height() != dst.height() {
+            return Err(Error::InvalidParms);
+        }
+        for y in 0..self.height() {
+            let src_row = self.row(y).as_ptr() as *const P;
+            let dst_row = dst.row_mut(y).as_mut_ptr() as *mut P;
+            for x in 0..self.width() {
+                unsafe { *dst_row.add(x) = f(*src_row.add(x)) };
+            }
+        }
+        Ok(())
+    }
+
+    fn map_in_place<P: Copy>(&mut self, world_type: aegp::WorldType, f: impl Fn(P) -> P) -> Result<(), Error> {
+        if self.world_type() != world_type {
+            return Err(Error::InvalidParms);
+        }
+        let width = self.width();
+        for y in 0..self.height() {
+            let row = self.row_mut(y).as_mut_ptr() as *mut P;
+            for x in 0..width {
+                unsafe { *row.add(x) = f(*row.add(x)) };
+            }
+        }
+        Ok(())
+    }
+
+    // ―――――――――――――――――――――――――――――――――――――― Channels ―――――――――――――――――――――――――――――――――――――
+
+    /// Splits this 8-bit layer into four planes in `[alpha, red, green, blue]` order, each `width * height` values row by row.
+    ///
+    /// Returns [`Error::InvalidParms`] if the layer is not 8-bit.
+    pub fn split_channels8(&self) -> Result<[Vec<u8>; 4], Error> {
+        self.split_channels(aegp::WorldType::U8, |p: Pixel8| [p.alpha, p.red, p.green, p.blue])
+    }
+
+    /// Splits this 16-bit layer into four planes in `[alpha, red, green, blue]` order, see [`split_channels8()`](Self::split_channels8).
+    pub fn split_channels16(&self) -> Result<[Vec<u16>; 4], Error> {
+        self.split_channels(aegp::WorldType::U15, |p: Pixel16| [p.alpha, p.red, p.green, p.blue])
+    }
+
+    /// Splits this float layer into four planes in `[alpha, red, green, blue]` order, see [`split_channels8()`](Self::split_channels8).
+    pub fn split_channels32(&self) -> Result<[Vec<f32>; 4], Error> {
+        self.split_channels(aegp::WorldType::F32, |p: PixelF32| [p.alpha, p.red, p.green, p.blue])
+    }
+
+    /// Writes four planes in `[alpha, red, green, blue]` order, as returned by [`split_channels8()`](Self::split_channels8), back into this 8-bit layer.
+    ///
+    /// Returns [`Error::InvalidParms`] if the layer is not 8-bit or a plane doesn't have `width * height` values.
+    pub fn merge_channels8(&mut self, planes: &[Vec<u8>; 4]) -> Result<(), Error> {
+        self.merge_channels(aegp::WorldType::U8, planes, |[alpha, red, green, blue]| Pixel8 { alpha, red, green, blue })
+    }
+
+    /// Writes four planes back into this 16-bit layer, see [`merge_channels8()`](Self::merge_channels8).
+    pub fn merge_channels16(&mut self, planes: &[Vec<u16>; 4]) -> Result<(), Error> {
+        self.merge_channels(aegp::WorldType::U15, planes, |[alpha, red, green, blue]| Pixel16 { alpha, red, green, blue })
+    }
+
+    /// Writes four planes back into this float layer, see [`merge_channels8()`](Self::merge_channels8).
+    pub fn merge_channels32(&mut self, planes: &[Vec<f32>; 4]) -> Result<(), Error> {
+        self.merge_channels(aegp::WorldType::F32, planes, |[alpha, red, green, blue]| PixelF32 { alpha, red, green, blue })
+    }
+
+    fn split_channels<P: Copy, C: Copy>(&self, world_type: aegp::WorldType, split: impl Fn(P) -> [C; 4]) -> Result<[Vec<C>; 4], Error> {
+        if self.world_type() != world_type {
+            return Err(Error::InvalidParms);
+        }
+        let len = self.width() * self.height();
+        let mut planes = [Vec::with_capacity(len), Vec::with_capacity(len), Vec::with_capacity(len), Vec::with_capacity(len)];
+        for y in 0..self.height() {
+            let row = self.row(y).as_ptr() as *const P;
+            for x in 0..self.width() {
+                for (plane, value) in planes.iter_mut().zip(split(unsafe { *row.add(x) })) {
+                    plane.push(value);
+                }
+            }
+        }
+        Ok(planes)
+    }
+
+    fn merge_channels<P: Copy, C: Copy>(&mut self, world_type: aegp::WorldType, planes: &[Vec<C>; 4], merge: impl Fn([C; 4]) -> P) -> Result<(), Error> {
+        let width = self.width();
+        if self.world_type() != world_type || planes.iter().any(|plane| plane.len() != width * self.height()) {
+            return Err(Error::InvalidParms);
+        }
+        for y in 0..self.height() {
+            let row = self.row_mut(y).as_mut_ptr() as *mut P;
+            for x in 0..width {
+                let i = y * width + x;
+                unsafe { *row.add(x) = merge([planes[0][i], planes[1][i], planes[2][i], planes[3][i]]) };
+            }
+        }
+        Ok(())
+    }
+
+    /// Replaces the alpha of this layer with `channel` of `matte`, like a set matte or track matte.
+    ///
+    /// Premultiplied layers keep their straight color, so the color is rescaled to the new alpha; pixels that were fully transparent
+    /// stay black. `matte` may have any bit depth but must have the same dimensions, otherwise [`Error::InvalidParms`] is returned.
+    pub fn set_alpha_from(&mut self, matte: &Layer, channel: PixelChannel) -> Result<(), Error> {
+        if !self.same_size(matte) {
+            return Err(Error::InvalidParms);
+        }
+        let premultiplied = self.premultiplied();
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let alpha = channel.value(matte.pixel_f32(x, y)).clamp(0.0, 1.0);
+                let p = with_alpha(self.pixel_f32(x, y), alpha, premultiplied);
+                self.set_pixel_f32(x, y, p);
+            }
+        }
+        Ok(())
+    }
+
+    // ――――――――――――――――――――――――――――――――――――――― Color ―――――――――――――――――――――――――――――――――――――――
+
+    /// Applies a levels adjustment to the color channels: values are remapped so that `black` becomes 0 and `white` becomes 1, then gamma corrected.
+    ///
+    /// `black` and `white` are normalized to 0..1 regardless of the bit depth. Premultiplied pixels are adjusted on their straight color,
+    /// so semi-transparent edges don't darken, and fully transparent ones are left as is. Results are clamped to the range of the world type; float layers keep values above 1.
+    ///
+    /// Returns [`Error::InvalidParms`] if `white` is not greater than `black` or `gamma` is not positive.
+    pub fn apply_levels(&mut self, black: f32, white: f32, gamma: f32) -> Result<(), Error> {
+        if white <= black || gamma <= 0.0 {
+            return Err(Error::InvalidParms);
+        }
+        let scale = 1.0 / (white - black);
+        let exponent = 1.0 / gamma;
+        self.map_straight_color(|rgb| rgb.map(|v| ((v - black) * scale).max(0.0).powf(exponent)));
+        Ok(())
+    }
+
+    /// Decodes sRGB encoded color channels to linear light, e.g. before blurring. Alpha is left as is.
+    ///
+    /// Uses the piecewise sRGB transfer function including its linear toe, premultiplied pixels are converted on their straight color.
+    pub fn to_linear(&mut self) {
+        self.map_straight_color(|rgb| rgb.map(srgb_to_linear));
+    }
+
+    /// Encodes linear color channels with the sRGB transfer function, the inverse of [`to_linear()`](Self::to_linear).
+    pub fn to_srgb(&mut self) {
+        self.map_straight_color(|rgb| rgb.map(linear_to_srgb));
+    }
+
+    /// Maps the color channels through a 3D LUT with tetrahedral interpolation, see [`Lut3d`]. Alpha is left as is.
+    ///
+    /// Premultiplied pixels are looked up by their straight color, fully transparent ones are left as is. The result is clamped to the
+    /// range of the world type.
+    pub fn apply_lut3d(&mut self, lut: &Lut3d) -> Result<(), Error> {
+        self.map_straight_color(|rgb| lut.sample(rgb));
+        Ok(())
+    }
+
+    /// Adds uniform noise in `-amount..amount` (normalized to 0..1 regardless of the bit depth) to the color channels. Alpha is left as is.
+    ///
+    /// The noise comes from a [`fastrand::Rng`] seeded with `seed`, walking the layer row by row, so the same seed and layer give
+    /// identical results, e.g. derive the seed from the current time to keep cached frames consistent. With `monochrome`, red, green
+    /// and blue get the same value. Premultiplied pixels get the noise on their straight color, fully transparent ones are left as is.
+    pub fn add_noise(&mut self, amount: f32, seed: u64, monochrome: bool) {
+        let mut rng = fastrand::Rng::with_seed(seed);
+        let mut noise = move || (rng.f32() * 2.0 - 1.0) * amount;
+        self.map_straight_color(|[r, g, b]| {
+            let (nr, ng, nb) = if monochrome { let n = noise(); (n, n, n) } else { (noise(), noise(), noise()) };
+            [r + nr, g + ng, b + nb]
+        });
+    }
+
+    /// Calls `f` with the straight color of every pixel, row by row, and stores the result. Alpha is left as is.
+    ///
+    /// Premultiplied pixels are unpremultiplied for `f` and premultiplied again afterwards; fully transparent ones are skipped.
+    fn map_straight_color(&mut self, mut f: impl FnMut([f32; 3]) -> [f32; 3]) {
+        let premultiplied = self.premultiplied();
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let mut p = self.pixel_f32(x, y);
+                if premultiplied && p.alpha <= 0.0 {
+                    continue;
+                }
+                let alpha = if premultiplied && p.alpha < 1.0 { p.alpha } else { 1.0 };
+                let [r, g, b] = f([p.red / alpha, p.green / alpha, p.blue / alpha]);
+                (p.red, p.green, p.blue) = (r * alpha, g * alpha, b * alpha);
+                self.set_pixel_f32(x, y, p);
+            }
+        }
+    }
+
+    /// Rec. 709 luma weights for red, green and blue, used by [`extract_luminance()`](Self::extract_luminance).
+    pub const REC709_LUMA_WEIGHTS: [f32; 3] = [0.2126, 0.7152, 0.0722];
+
+    /// Rec. 601 luma weights for red, green and blue, for standard definition and legacy content.
+    pub const REC601_LUMA_WEIGHTS: [f32; 3] = [0.299, 0.587, 0.114];
+
+    /// Returns the average Rec. 709 luminance of the stored colors, see [`mean_luma_with()`](Self::mean_luma_with).
+    pub fn mean_luma(&self) -> f32 {
+        self.mean_luma_with(Self::REC709_LUMA_WEIGHTS)
+    }
+
+    /// Returns the average luminance of the stored colors computed with the given red, green and blue `weights`, or 0 for an empty layer.
+    ///
+    /// Like [`extract_luminance_with()`](Self::extract_luminance_with), premultiplied layers are measured as composited over black.
+    pub fn mean_luma_with(&self, weights: [f32; 3]) -> f32 {
+        let (width, height) = (self.width(), self.height());
+        if width == 0 || height == 0 {
+            return 0.0;
+        }
+        let [wr, wg, wb] = weights;
+        let mut sum = 0.0f64;
+        for y in 0..height {
+            for x in 0..width {
+                let p = self.pixel_f32(x, y);
+                sum += (wr * p.red + wg * p.green + wb * p.blue) as f64;
+            }
+        }
+        (sum / (width * height) as f64) as f32
+    }
+
+    /// Writes the alpha channel into `dst` as an opaque grayscale matte.
+    ///
+    /// `dst` may have a different bit depth but must have the same dimensions, otherwise [`Error::InvalidParms`] is returned.
+    pub fn extract_alpha(&self, dst: &mut Layer) -> Result<(), Error> {
+        self.extract_matte(dst, |p| p.alpha)
+    }
+
+    /// Writes the Rec. 709 luminance into `dst` as an opaque grayscale matte, see [`extract_luminance_with()`](Self::extract_luminance_with).
+    pub fn extract_luminance(&self, dst: &mut Layer) -> Result<(), Error> {
+        self.extract_luminance_with(dst, Self::REC709_LUMA_WEIGHTS)
+    }
+
+    /// Writes the luminance computed with the given red, green and blue `weights` into `dst` as an opaque grayscale matte.
+    ///
+    /// The luminance is taken from the stored color, which for premultiplied layers is the layer composited over black.
+    /// `dst` may have a different bit depth but must have the same dimensions, otherwise [`Error::InvalidParms`] is returned.
+    pub fn extract_luminance_with(&self, dst: &mut Layer, weights: [f32; 3]) -> Result<(), Error> {
+        let [wr, wg, wb] = weights;
+        self.extract_matte(dst, |p| wr * p.red + wg * p.green + wb * p.blue)
+    }
+
+    fn extract_matte(&self, dst: &mut Layer, f: impl Fn(PixelF32) -> f32) -> Result<(), Error> {
+        if self.width() != dst.width() || self.height() != dst.height() {
+            return Err(Error::InvalidParms);
+        }
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let v = f(self.pixel_f32(x, y));
+                dst.set_pixel_f32(x, y, PixelF32 { alpha: 1.0, red: v, green: v, blue: v });
+            }
+        }
+        Ok(())
+    }
+
+    /// Turns this layer into a binary matte: pixels whose `channel` is at least `level` become opaque white, all others transparent black.
+    ///
+    /// `level` is normalized to 0..1 regardless of the bit depth, `invert` swaps the two results. See [`soft_threshold()`](Self::soft_threshold)
+    /// for anti-aliased edges.
+    pub fn threshold(&mut self, channel: PixelChannel, level: f32, invert: bool) {
+        self.soft_threshold(channel, level, 0.0, invert);
+    }
+
+    /// Like [`threshold()`](Self::threshold), but ramps linearly from transparent to opaque over a `softness` wide range centered on `level`.
+    ///
+    /// A `softness` of 0 or less gives the hard threshold.
+    pub fn soft_threshold(&mut self, channel: PixelChannel, level: f32, softness: f32, invert: bool) {
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let v = channel.value(self.pixel_f32(x, y));
+                let mut matte = if softness > 0.0 {
+                    ((v - level) / softness + 0.5).clamp(0.0, 1.0)
+                } else if v >= level {
+                    1.0
+                } else {
+                    0.0
+                };
+                if invert {
+                    matte = 1.0 - matte;
+                }
+                self.set_pixel_f32(x, y, PixelF32 { alpha: matte, red: matte, green: matte, blue: matte });
+            }
+        }
+    }
+
+    // ――――――――――――――――――――――――――――――――――――― Compositing ――――――――――――――――――――――――――――――――――――
+
+    /// Composites `fg` onto this layer with its top left corner at `origin`, clipped to this layer.
+    ///
+    /// `opacity` (0..1) scales the alpha of `fg`. Both layers may have any bit depth, the math is done in float.
+    /// Supports the modes of [`TransferMode::combiner32()`], other modes return [`Error::InvalidParms`].
+    ///
+    /// To composite with the host's implementation instead, use [`pf::suites::WorldTransform::composite_rect`].
+    pub fn composite_over(&mut self, fg: &Layer, origin: Point, mode: TransferMode, opacity: f32) -> Result<(), Error> {
+        let combine = mode.combiner32().ok_or(Error::InvalidParms)?;
+        let opacity = opacity.clamp(0.0, 1.0);
+        let fg_premultiplied = fg.premultiplied();
+        let dst_premultiplied = self.premultiplied();
+
+        let x0 = (origin.h as isize).max(0) as usize;
+        let y0 = (origin.v as isize).max(0) as usize;
+        let x1 = (origin.h as isize + fg.width() as isize).clamp(0, self.width() as isize) as usize;
+        let y1 = (origin.v as isize + fg.height() as isize).clamp(0, self.height() as isize) as usize;
+
+        for y in y0..y1 {
+            let fy = (y as isize - origin.v as isize) as usize;
+            for x in x0..x1 {
+                let fx = (x as isize - origin.h as isize) as usize;
+                let out = composite_pixel(fg.pixel_f32(fx, fy), fg_premultiplied, self.pixel_f32(x, y), dst_premultiplied, mode, opacity, &combine);
+                self.set_pixel_f32(x, y, out);
+            }
+        }
+        Ok(())
+    }
+
+    /// Composites `fg` onto this layer like [`composite_over()`](Self::composite_over), with the opacity of every pixel taken from `mask`.
+    ///
+    /// The mask value is `mask_channel` of the mask pixel, usually [`PixelChannel::Alpha`] or [`PixelChannel::Luminance`].
+    /// All three layers must have the same dimensions and `fg` the same world type as this layer, otherwise [`Error::InvalidParms`]
+    /// is returned. The mask may have any bit depth.
+    pub fn composite_masked(&mut self, fg: &Layer, mask: &Layer, mask_channel: PixelChannel, mode: TransferMode) -> Result<(), Error> {
+        let combine = mode.combiner32().ok_or(Error::InvalidParms)?;
+        if fg.world_type() != self.world_type() || !self.same_size(fg) || !self.same_size(mask) {
+            return Err(Error::InvalidParms);
+        }
+        let fg_premultiplied = fg.premultiplied();
+        let dst_premultiplied = self.premultiplied();
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let opacity = mask_channel.value(mask.pixel_f32(x, y)).clamp(0.0, 1.0);
+                if opacity == 0.0 {
+                    continue;
+                }
+                let out = composite_pixel(fg.pixel_f32(x, y), fg_premultiplied, self.pixel_f32(x, y), dst_premultiplied, mode, opacity, &combine);
+                self.set_pixel_f32(x, y, out);
+            }
+        }
+        Ok(())
+    }
+
+    fn same_size(&self, other: &Layer) -> bool {
+        self.width() == other.width() && self.height() == other.height()
+    }
+
+    /// Multiplies this layer by `mask`, whose top left corner is at `mask.offset` in this layer.
+    ///
+    /// The mask value is the alpha of the mask world, or its Rec. 709 luminance for [`MaskFlags::Luminance`], and is
+    /// inverted for [`MaskFlags::Inverted`]. Pixels outside the mask world count as outside the mask. Premultiplied layers have
+    /// all channels scaled, straight layers only their alpha. The two worlds may have different bit depths.
+    pub fn apply_mask(&mut self, mask: &MaskWorld) -> Result<(), Error> {
+        let mask_layer = Layer::from_owned(mask.mask, self.in_data_ptr, |_| {});
+        let inverted = mask.what_is_mask == MaskFlags::Inverted;
+        let premultiplied = self.premultiplied();
+        let (mask_w, mask_h) = (mask_layer.width() as i64, mask_layer.height() as i64);
+        let [wr, wg, wb] = Self::REC709_LUMA_WEIGHTS;
+
+        for y in 0..self.height() {
+            let my = y as i64 - mask.offset.v as i64;
+            for x in 0..self.width() {
+                let mx = x as i64 - mask.offset.h as i64;
+                let mut value = if mx < 0 || my < 0 || mx >= mask_w || my >= mask_h {
+                    0.0
+                } else {
+                    let m = mask_layer.pixel_f32(mx as usize, my as usize);
+                    match mask.what_is_mask {
+                        MaskFlags::Luminance => wr * m.red + wg * m.green + wb * m.blue,
+                        _                    => m.alpha,
+                    }
+                };
+                if inverted {
+                    value = 1.0 - value;
+                }
+                let value = value.clamp(0.0, 1.0);
+                if value == 1.0 {
+                    continue;
+                }
+                let mut p = self.pixel_f32(x, y);
+                p.alpha *= value;
+                if premultiplied {
+                    p.red   *= value;
+                    p.green *= value;
+                    p.blue  *= value;
+                }
+                self.set_pixel_f32(x, y, p);
+            }
+        }
+        Ok(())
+    }
+
+    // ―――――――――――――――――――――――――――――――――――――― Drawing ――――――――――――――――――――――――――――――――――――――
+
+    /// Composites `px` over the pixel at `x`, `y` with the given `opacity` (0..1), at any bit depth.
+    ///
+    /// `px` is straight (not premultiplied). Coordinates outside the layer are ignored.
+    pub fn set_pixel_blended8(&mut self, x: i32, y: i32, px: Pixel8, opacity: f32) {
+        if x < 0 || y < 0 || x as usize >= self.width() || y as usize >= self.height() {
+            return;
+        }
+        let (x, y) = (x as usize, y as usize);
+        let src = pixel8_to_f32(px);
+        let alpha = src.alpha * opacity.clamp(0.0, 1.0);
+        let t = 1.0 - alpha;
+        let dst = self.pixel_f32(x, y);
+        let out = if self.premultiplied() {
+            PixelF32 {
+                alpha: alpha + dst.alpha * t,
+                red:   src.red   * alpha + dst.red   * t,
+                green: src.green * alpha + dst.green * t,
+                blue:  src.blue  * alpha + dst.blue  * t,
+            }
+        } else {
+            let out_alpha = alpha + dst.alpha * t;
+            let mix = |s: f32, d: f32| if out_alpha > 0.0 { (s * alpha + d * dst.alpha * t) / out_alpha } else { 0.0 };
+            PixelF32 { alpha: out_alpha, red: mix(src.red, dst.red), green: mix(src.green, dst.green), blue: mix(src.blue, dst.blue) }
+        };
+        self.set_pixel_f32(x, y, out);
+    }
+
+    /// Draws a one pixel wide line from `a` to `b` (both inclusive) with [`set_pixel_blended8()`](Self::set_pixel_blended8).
+    ///
+    /// Without `anti_aliased` every step along the major axis gets the pixel nearest to the line, like a Bresenham line,
+    /// otherwise the coverage of the two pixels nearest to the line is blended in (Xiaolin Wu's algorithm). The line is clipped
+    /// to the layer along its major axis before it's walked, so the cost is bounded by the layer size and any endpoints are fine.
+    pub fn draw_line8(&mut self, a: Point, b: Point, px: Pixel8, opacity: f32, anti_aliased: bool) {
+        let (ax, ay, bx, by) = (a.h as i64, a.v as i64, b.h as i64, b.v as i64);
+        let steep = (by - ay).abs() > (bx - ax).abs();
+        let (mut a, mut b) = if steep { ((ay, ax), (by, bx)) } else { ((ax, ay), (bx, by)) };
+        if a.0 > b.0 {
+            std::mem::swap(&mut a, &mut b);
+        }
+        let (major_len, minor_len) = if steep { (self.height(), self.width()) } else { (self.width(), self.height()) };
+        let (major_len, minor_len) = (major_len as i64, minor_len as i64);
+        // `run` is at least as long as `rise`, and zero only for a single point.
+        let (run, rise) = (b.0 - a.0, b.1 - a.1);
+
+        let mut plot = |major: i64, minor: i64, coverage: f32| {
+            if (0..minor_len).contains(&minor) {
+                let (x, y) = if steep { (minor, major) } else { (major, minor) };
+                self.set_pixel_blended8(x as i32, y as i32, px, opacity * coverage);
+            }
+        };
+        // Only walk the part of the major axis covered by the layer.
+        for major in a.0.max(0)..=b.0.min(major_len - 1) {
+            if !anti_aliased {
+                let minor = if run == 0 {
+                    a.1
+                } else {
+                    // Rounds `rise * (major - a.0) / run` to the nearest integer, in `i128` as the product may not fit in `i64`.
+                    let num = rise as i128 * (major - a.0) as i128;
+                    a.1 + (2 * num + run as i128).div_euclid(2 * run as i128) as i64
+                };
+                plot(major, minor, 1.0);
+            } else {
+                let gradient = if run == 0 { 0.0 } else { rise as f64 / run as f64 };
+                let minor = a.1 as f64 + gradient * (major - a.0) as f64;
+                let base = minor.floor();
+                let frac = (minor - base) as f32;
+                if base < -1.0 || base >= minor_len as f64 {
+                    continue;
+                }
+                plot(major, base as i64, 1.0 - frac);
+                if frac > 0.0 {
+                    plot(major, base as i64 + 1, frac);
+                }
+            }
+        }
+    }
+
+    /// Fills `rect` (`right` and `bottom` exclusive) with `px`, converted to the bit depth of the layer.
+    ///
+    /// Unlike [`fill()`](Self::fill) this doesn't go through the host, and `rect` is clipped to the layer, so an empty intersection
+    /// is a no-op. The row padding is left untouched.
+    pub fn fill_rect8(&mut self, rect: Rect, px: Pixel8) {
+        self.fill_rect_with(rect, px, pixel8_to_16(px), pixel8_to_f32(px));
+    }
+
+    /// Like [`fill_rect8()`](Self::fill_rect8) but with a 16 bit pixel.
+    pub fn fill_rect16(&mut self, rect: Rect, px: Pixel16) {
+        self.fill_rect_with(rect, pixel16_to_8(px), px, pixel16_to_f32(px));
+    }
+
+    /// Like [`fill_rect8()`](Self::fill_rect8) but with a float pixel.
+    pub fn fill_rect32(&mut self, rect: Rect, px: PixelF32) {
+        self.fill_rect_with(rect, pixel_f32_to_8(px), pixel_f32_to_16(px), px);
+    }
+
+    fn fill_rect_with(&mut self, rect: Rect, px8: Pixel8, px16: Pixel16, px32: PixelF32) {
+        let x0 = rect.left.clamp(0, self.width() as i32) as usize;
+        let y0 = rect.top.clamp(0, self.height() as i32) as usize;
+        let x1 = rect.right.clamp(0, self.width() as i32) as usize;
+        let y1 = rect.bottom.clamp(0, self.height() as i32) as usize;
+        let world_type = self.world_type();
+        for y in y0..y1 {
+            for x in x0..x1 {
+                match world_type {
+                    aegp::WorldType::U15 => *self.as_pixel16_mut(x, y) = px16,
+                    aegp::WorldType::F32 => *self.as_pixel32_mut(x, y) = px32,
+                    _                    => *self.as_pixel8_mut(x, y) = px8,
+                }
+            }
+        }
+    }
+
+    /// Creates a [`new_in_memory()`](Self::new_in_memory) world filled with `color`, converted to `world_type`.
+    pub fn solid(width: usize, height: usize, color: PixelF32, world_type: aegp::WorldType) -> Result<Self, Error> {
+        let mut layer = Self::new_in_memory(width, height, world_type)?;
+        layer.fill_rect32(Rect { left: 0, top: 0, right: width as _, bottom: height as _ }, color);
+        Ok(layer)
+    }
+
+    /// Creates a [`new_in_memory()`](Self::new_in_memory) world with a checkerboard of `cell`x`cell` squares, converted to
+    /// `world_type`. The top left square is `c0`.
+    ///
+    /// Useful as synthetic input, or as a backdrop to visualize alpha in custom UI. Returns [`Error::InvalidParms`] if `cell` is 0.
+    pub fn checkerboard(width: usize, height: usize, cell: u32, c0: PixelF32, c1: PixelF32, world_type: aegp::WorldType) -> Result<Self, Error> {
+        if cell == 0 {
+            return Err(Error::InvalidParms);
+        }
+        let mut layer = Self::solid(width, height, c0, world_type)?;
+        let cell = cell as i32;
+        for (row, top) in (0..height as i32).step_by(cell as usize).enumerate() {
+            for left in (0..width as i32).step_by(cell as usize).skip(1 - row % 2).step_by(2) {
+                layer.fill_rect32(Rect { left, top, right: left + cell, bottom: top + cell }, c1);
+            }
+        }
+        Ok(layer)
+    }
+
+    // ―――――――――――――――――――――――――――――――――――――― Bounds ――――――――――――――――――――――――――――――――――――――
+
+    /// Returns the tightest rectangle containing all pixels with non-zero alpha, or `None` if the layer is fully transparent.
+    ///
+    /// Like [`extent_hint()`](Self::extent_hint), `right` and `bottom` are exclusive.
+    pub fn alpha_bounds(&self) -> Option<Rect> {
+        let (width, height) = (self.width(), self.height());
+        let row_has_alpha = |y: usize| (0..width).any(|x| self.has_alpha(x, y));
+        let column_has_alpha = |x: usize, top: usize, bottom: usize| (top..bottom).any(|y| self.has_alpha(x, y));
+
+        let top = (0..height).find(|&y| row_has_alpha(y))?;
+        let bottom = (top..height).rev().find(|&y| row_has_alpha(y)).unwrap_or(top) + 1;
+        let left = (0..width).find(|&x| column_has_alpha(x, top, bottom)).unwrap_or(0);
+        let right = (left..width).rev().find(|&x| column_has_alpha(x, top, bottom)).unwrap_or(left) + 1;
+
+        Some(Rect { left: left as i32, top: top as i32, right: right as i32, bottom: bottom as i32 })
+    }
+
+    fn has_alpha(&self, x: usize, y: usize) -> bool {
+        match self.world_type() {
+            aegp::WorldType::U15 => self.as_pixel16(x, y).alpha != 0,
+            aegp::WorldType::F32 => self.as_pixel32(x, y).alpha > 0.0,
+            _                    => self.as_pixel8(x, y).alpha != 0,
+        }
+    }
+
+    /// Returns the pixel data of row `y`, without the row padding.
+    pub fn row(&self, y: usize) -> &[u8] {
+        assert!(y < self.height());
+        let len = self.width() * Self::bytes_per_pixel(self.world_type());
+        unsafe { std::slice::from_raw_parts(self.data_ptr().offset(y as isize * self.row_bytes()), len) }
+    }
+
+    /// Returns the mutable pixel data of row `y`, without the row padding.
+    pub fn row_mut(&mut self, y: usize) -> &mut [u8] {
+        assert!(y < self.height());
+        let len = self.width() * Self::bytes_per_pixel(self.world_type());
+        unsafe { std::slice::from_raw_parts_mut(self.data_ptr_mut().offset(y as isize * self.row_bytes()), len) }
+    }
+
+    /// Returns whether `other` has the same size, type and pixel bytes as this layer, e.g. to skip reprocessing an unchanged input.
+    ///
+    /// Compares [`row()`](Self::row) by row, so the row padding and the row stride don't matter, and stops at the first difference.
+    /// Float pixels are compared bitwise, so `-0.0` and `0.0` differ and identical NaNs match.
+    pub fn is_identical_to(&self, other: &Layer) -> bool {
+        if self.width() != other.width() || self.height() != other.height() || self.world_type() != other.world_type() {
+            return false;
+        }
+        if self.width() == 0 {
+            return true;
+        }
+        (0..self.height()).all(|y| self.row(y) == other.row(y))
+    }
+
+    // ―――――――――――――――――――――――――――――――――― Flip & transpose ――――――――――――――――――――――――――――――――――
+
+    /// Mirrors the layer in place around its vertical axis.
+    pub fn flip_horizontal(&mut self) {
+        let bpp = Self::bytes_per_pixel(self.world_type());
+        for y in 0..self.height() {
+            let row = self.row_mut(y);
+            let width = row.len() / bpp;
+            for x in 0..width / 2 {
+                let (left, right) = row.split_at_mut((width - 1 - x) * bpp);
+                left[x * bpp..(x + 1) * bpp].swap_with_slice(&mut right[..bpp]);
+            }
+        }
+    }
+
+    /// Mirrors the layer in place around its horizontal axis.
+    pub fn flip_vertical(&mut self) {
+        let height = self.height();
+        if height < 2 {
+            return;
+        }
+        let len = self.row(0).len();
+        for y in 0..height / 2 {
+            let top = self.row_mut(y).as_mut_ptr();
+            let bottom = self.row_mut(height - 1 - y).as_mut_ptr();
+            unsafe { std::ptr::swap_nonoverlapping(top, bottom, len) };
+        }
+    }
+
+    /// Writes the transposed layer into `dst`, so that `dst(y, x) = self(x, y)`.
+    ///
+    /// `dst` must have the same world type and swapped dimensions, otherwise [`Error::InvalidParms`] is returned.
+    pub fn transpose_into(&self, dst: &mut Layer) -> Result<(), Error> {
+        if self.world_type() != dst.world_type() || self.width() != dst.height() || self.height() != dst.width() {
+            return Err(Error::InvalidParms);
+        }
+        let bpp = Self::bytes_per_pixel(self.world_type());
+        for y in 0..self.height() {
+            let src_row = self.row(y);
+            for x in 0..self.width() {
+                dst.row_mut(x)[y * bpp..(y + 1) * bpp].copy_from_slice(&src_row[x * bpp..(x + 1) * bpp]);
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes this layer rotated clockwise by `turns` quarter turns into `dst`. `turns` is taken modulo 4, 0 copies the layer.
+    ///
+    /// `dst` must have the same world type and the rotated dimensions (width and height swapped for 1 and 3 turns),
+    /// otherwise [`Error::InvalidParms`] is returned. To rotate by 180° in place, use [`rotate_180()`](Self::rotate_180).
+    pub fn rotate_90(&self, dst: &mut Layer, turns: u8) -> Result<(), Error> {
+        let turns = turns % 4;
+        let (w, h) = (self.width(), self.height());
+        let (dst_w, dst_h) = if turns % 2 == 1 { (h, w) } else { (w, h) };
+        if self.world_type() != dst.world_type() || dst.width() != dst_w || dst.height() != dst_h {
+            return Err(Error::InvalidParms);
+        }
+        let bpp = Self::bytes_per_pixel(self.world_type());
+        for y in 0..h {
+            let src_row = self.row(y);
+            for x in 0..w {
+                let (dx, dy) = match turns {
+                    0 => (x, y),
+                    1 => (h - 1 - y, x),
+                    2 => (w - 1 - x, h - 1 - y),
+                    _ => (y, w - 1 - x),
+                };
+                dst.row_mut(dy)[dx * bpp..(dx + 1) * bpp].copy_from_slice(&src_row[x * bpp..(x + 1) * bpp]);
+            }
+        }
+        Ok(())
+    }
+
+    /// Rotates the layer by 180° in place.
+    pub fn rotate_180(&mut self) {
+        self.flip_horizontal();
+        self.flip_vertical();
+    }
+
+    // ―――――――――――――――――――――――――――――――――――――― Debugging ――――――――――――――――――――――――――――――――――――――
+
+    /// Renders a small ASCII preview of the luminance of the stored colors, one line per row of characters.
+    ///
+    /// The layer is point-sampled down to at most `max_w` columns (capped at 256), and rows are halved to roughly
+    /// compensate for the aspect ratio of terminal characters. Meant for test output and logging.
+    pub fn dump_ascii(&self, max_w: usize) -> String {
+        const RAMP: &[u8] = b" .:-=+*#%@";
+        let (width, height) = (self.width(), self.height());
+        if width == 0 || height == 0 || max_w == 0 {
+            return String::new();
+        }
+        let cols = max_w.min(256).min(width);
+        let rows = (height * cols / width / 2).clamp(1, height);
+
+        let mut out = String::with_capacity((cols + 1) * rows);
+        for row in 0..rows {
+            let y = (row * height + height / 2) / rows;
+            for col in 0..cols {
+                let x = (col * width + width / 2) / cols;
+                let p = self.pixel_f32(x.min(width - 1), y.min(height - 1));
+                let [wr, wg, wb] = Self::REC709_LUMA_WEIGHTS;
+                let luma = wr * p.red + wg * p.green + wb * p.blue;
+                let index = (luma.clamp(0.0, 1.0) * (RAMP.len() - 1) as f32).round() as usize;
+                out.push(RAMP[index] as char);
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl TransferMode {
+    /// Returns the compositing function of this mode as `f(src, dst)`, taking and returning premultiplied float pixels.
+    ///
+    /// * [`TransferMode::Copy`] returns `src`.
+    /// * [`TransferMode::InFront`] is normal source-over, [`TransferMode::Behind`] puts `src` behind `dst`.
+    /// * The separable blend modes `Add`, `Mulitply`, `Screen`, `Overlay`, `SoftLight`, `HardLight`, `Darken`, `Lighten`,
+    ///   `Difference`, `Exclusion`, `ColorDodge`, `ColorBurn`, `LinearDodge`, `LinearBurn`, `Subtract` and `Divide`
+    ///   blend the straight colors and composite the result source-over, following the W3C compositing spec.
+    ///
+    /// Returns `None` for the other modes (dissolve, the hue/saturation/color/luminosity modes, the alpha modes, ...),
+    /// which are best left to the host, e.g. with [`pf::suites::WorldTransform::composite_rect`].
+    pub fn combiner32(self) -> Option<impl Fn(PixelF32, PixelF32) -> PixelF32> {
+        let blend: Option<fn(f32, f32) -> f32> = match self {
+            TransferMode::Copy | TransferMode::InFront | TransferMode::Behind => None,
+            TransferMode::Add | TransferMode::LinearDodge => Some(|b, s| b + s),
+            TransferMode::Mulitply   => Some(|b, s| b * s),
+            TransferMode::Screen     => Some(|b, s| b + s - b * s),
+            TransferMode::Overlay    => Some(|b, s| hard_light(s, b)),
+            TransferMode::HardLight  => Some(hard_light),
+            TransferMode::SoftLight  => Some(soft_light),
+            TransferMode::Darken     => Some(f32::min),
+            TransferMode::Lighten    => Some(f32::max),
+            TransferMode::Difference => Some(|b, s| (b - s).abs()),
+            TransferMode::Exclusion  => Some(|b, s| b + s - 2.0 * b * s),
+            TransferMode::ColorDodge => Some(|b, s| if b <= 0.0 { 0.0 } else if s >= 1.0 { 1.0 } else { (b / (1.0 - s)).min(1.0) }),
+            TransferMode::ColorBurn  => Some(|b, s| if b >= 1.0 { 1.0 } else if s <= 0.0 { 0.0 } else { 1.0 - ((1.0 - b) / s).min(1.0) }),
+            TransferMode::LinearBurn => Some(|b, s| (b + s - 1.0).max(0.0)),
+            TransferMode::Subtract   => Some(|b, s| (b - s).max(0.0)),
+            TransferMode::Divide     => Some(|b, s| if s <= 0.0 { 1.0 } else { b / s }),
+            _ => return None,
+        };
+        let mode = self;
+        Some(move |s: PixelF32, d: PixelF32| {
+            let over = |top: PixelF32, bottom: PixelF32| {
+                let t = 1.0 - top.alpha;
+                PixelF32 {
+                    alpha: top.alpha + bottom.alpha * t,
+                    red:   top.red   + bottom.red   * t,
+                    green: top.green + bottom.green * t,
+                    blue:  top.blue  + bottom.blue  * t,
+                }
+            };
+            match (mode, blend) {
+                (TransferMode::Copy, _)   => s,
+                (TransferMode::Behind, _) => over(d, s),
+                (_, None)                 => over(s, d),
+                (_, Some(blend)) => {
+                    let (sa, da) = (s.alpha, d.alpha);
+                    let channel = |sc: f32, dc: f32| {
+                        let cs = if sa > 0.0 { sc / sa } else { 0.0 };
+                        let cb = if da > 0.0 { dc / da } else { 0.0 };
+                        sa * da * blend(cb, cs) + sc * (1.0 - da) + dc * (1.0 - sa)
+                    };
+                    PixelF32 {
+                        alpha: sa + da * (1.0 - sa),
+                        red:   channel(s.red,   d.red),
+                        green: channel(s.green, d.green),
+                        blue:  channel(s.blue,  d.blue),
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// Composites `fg` over `dst` with `opacity`, returning the result in the premultiplication of `dst`.
+fn composite_pixel(fg: PixelF32, fg_premultiplied: bool, dst: PixelF32, dst_premultiplied: bool, mode: TransferMode, opacity: f32, combine: &impl Fn(PixelF32, PixelF32) -> PixelF32) -> PixelF32 {
+    let s = scale_pixel(to_premultiplied(fg, fg_premultiplied), opacity);
+    let d = to_premultiplied(dst, dst_premultiplied);
+    let mut out = if mode == TransferMode::Copy {
+        // Copy replaces the destination, so opacity fades between the two instead of scaling the source alpha.
+        let d = scale_pixel(d, 1.0 - opacity);
+        PixelF32 { alpha: s.alpha + d.alpha, red: s.red + d.red, green: s.green + d.green, blue: s.blue + d.blue }
+    } else {
+        combine(s, d)
+    };
+    if !dst_premultiplied && out.alpha > 0.0 {
+        let alpha = out.alpha;
+        out = scale_pixel(out, 1.0 / alpha);
+        out.alpha = alpha;
+    }
+    out
+}
+
+fn to_premultiplied(p: PixelF32, premultiplied: bool) -> PixelF32 {
+    if premultiplied {
+        p
+    } else {
+        PixelF32 { alpha: p.alpha, ..scale_pixel(p, p.alpha) }
+    }
+}
+
+/// Replaces the alpha of `p`, rescaling the color of premultiplied pixels so they keep their straight color. Transparent ones stay black.
+fn with_alpha(p: PixelF32, alpha: f32, premultiplied: bool) -> PixelF32 {
+    if premultiplied {
+        let scale = if p.alpha > 0.0 { alpha / p.alpha } else { 0.0 };
+        PixelF32 { alpha, ..scale_pixel(p, scale) }
+    } else {
+        PixelF32 { alpha, ..p }
+    }
+}
+
+fn scale_pixel(p: PixelF32, factor: f32) -> PixelF32 {
+    PixelF32 { alpha: p.alpha * factor, red: p.red * factor, green: p.green * factor, blue: p.blue * factor }
+}
+
+/// Returns a normalized Gaussian kernel of `2 * ceil(3 * sigma) + 1` weights.
+fn gaussian_kernel(sigma: f32) -> Vec<f32> {
+    if sigma <= 0.0 {
+        return vec![1.0];
+    }
+    let radius = (sigma * 3.0).ceil() as isize;
+    let kernel: Vec<f32> = (-radius..=radius).map(|i| (-((i * i) as f32) / (2.0 * sigma * sigma)).exp()).collect();
+    let sum: f32 = kernel.iter().sum();
+    kernel.into_iter().map(|w| w / sum).collect()
+}
+
+fn srgb_to_linear(v: f32) -> f32 {
+    if v <= 0.04045 { v / 12.92 } else { ((v + 0.055) / 1.055).powf(2.4) }
+}
+
+fn linear_to_srgb(v: f32) -> f32 {
+    if v <= 0.0031308 { v * 12.92 } else { 1.055 * v.powf(1.0 / 2.4) - 0.055 }
+}
+
+fn hard_light(b: f32, s: f32) -> f32 {
+    if s <= 0.5 { 2.0 * b * s } else { 1.0 - 2.0 * (1.0 - b) * (1.0 - s) }
+}
+
+fn soft_light(b: f32, s: f32) -> f32 {
+    if s <= 0.5 {
+        b - (1.0 - 2.0 * s) * b * (1.0 - b)
+    } else {
+        let d = if b <= 0.25 { ((16.0 * b - 12.0) * b + 4.0) * b } else { b.sqrt() };
+        b + (2.0 * s - 1.0) * (d - b)
+    }
+}
+
+#[cfg(feature = "image")]
+impl Layer {
+    /// Copies the layer into an 8-bit RGBA [`image::RgbaImage`], reordering the channels and dropping the row padding.
+    ///
+    /// 16-bit and float layers are converted to 8 bits per channel. Color values are copied as-is, without un-premultiplying.
+    pub fn to_image_buffer(&self) -> Result<image::RgbaImage, Error> {
+        let (width, height) = (self.width(), self.height());
+        let mut buffer = Vec::with_capacity(width * height * 4);
+        for y in 0..height {
+            for x in 0..width {
+                let px = match self.world_type() {
+                    aegp::WorldType::U8 => *self.as_pixel8(x, y),
+                    _                   => pixel_f32_to_8(self.pixel_f32(x, y)),
+                };
+                buffer.extend_from_slice(&[px.red, px.green, px.blue, px.alpha]);
+            }
+        }
+        image::RgbaImage::from_raw(width as u32, height as u32, buffer).ok_or(Error::InternalStructDamaged)
+    }
+
+    /// Writes an 8-bit RGBA [`image::RgbaImage`] into the layer, converting to the bit depth of the layer.
+    ///
+    /// The image must have the same dimensions as the layer, otherwise [`Error::InvalidParms`] is returned.
+    pub fn write_from_image_buffer(&mut self, image: &image::RgbaImage) -> Result<(), Error> {
+        if image.width() as usize != self.width() || image.height() as usize != self.height() {
+            return Err(Error::InvalidParms);
+        }
+        self.write_rgba8(image.as_raw())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layer8(width: usize, height: usize, data: &mut [Pixel8]) -> Layer {
+        let mut def: ae_sys::PF_LayerDef = unsafe { std::mem::zeroed() };
+        def.data = data.as_mut_ptr() as _;
+        def.width = width as _;
+        def.height = height as _;
+        def.rowbytes = (width * std::mem::size_of::<Pixel8>()) as _;
+        Layer::from_owned(def, std::ptr::null(), |_| {})
+    }
+
+    fn pixels(width: usize, height: usize, alpha: u8) -> Vec<Pixel8> {
+        vec![Pixel8 { alpha, red: 0, green: 0, blue: 0 }; width * height]
+    }
+
+    #[test]
+    fn alpha_bounds_opaque() {
+        let mut data = pixels(4, 3, 255);
+        let layer = layer8(4, 3, &mut data);
+        assert_eq!(layer.alpha_bounds(), Some(Rect { left: 0, top: 0, right: 4, bottom: 3 }));
+    }
+
+    #[test]
+    fn alpha_bounds_transparent() {
+        let mut data = pixels(4, 3, 0);
+        let layer = layer8(4, 3, &mut data);
+        assert_eq!(layer.alpha_bounds(), None);
+    }
+
+    #[test]
+    fn alpha_bounds_single_pixel() {
+        let mut data = pixels(5, 4, 0);
+        data[2 * 5 + 3].alpha = 1;
+        let layer = layer8(5, 4, &mut data);
+        assert_eq!(layer.alpha_bounds(), Some(Rect { left: 3, top: 2, right: 4, bottom: 3 }));
+    }
+
+    #[test]
+    fn convolve_clamps_edges() {
+        let mut src_data = pixels(3, 1, 255);
+        src_data[0].red = 200;
+        let mut dst_data = pixels(3, 1, 0);
+        let src = layer8(3, 1, &mut src_data);
+        let mut dst = layer8(3, 1, &mut dst_data);
+        src.convolve(&[0.25, 0.5, 0.25], 3, 1, &mut dst).unwrap();
+        let red: Vec<u8> = dst_data.iter().map(|p| p.red).collect();
+        assert_eq!(red, [150, 50, 0]);
+        assert!(dst_data.iter().all(|p| p.alpha == 255));
+    }
+
+    #[test]
+    fn composite_over_clips_to_destination() {
+        let mut dst_data = pixels(3, 2, 255);
+        let mut fg_data = vec![Pixel8 { alpha: 255, red: 255, green: 0, blue: 0 }; 2 * 2];
+        fg_data[0].alpha = 0;
+        fg_data[0].red = 0;
+        let mut dst = layer8(3, 2, &mut dst_data);
+        let fg = layer8(2, 2, &mut fg_data);
+        dst.composite_over(&fg, Point { h: 2, v: -1 }, TransferMode::InFront, 1.0).unwrap();
+        let red: Vec<u8> = dst_data.iter().map(|p| p.red).collect();
+        assert_eq!(red, [0, 0, 255, 0, 0, 0]);
+        assert!(dst_data.iter().all(|p| p.alpha == 255));
+    }
+
+    #[test]
+    fn combiner32_reference_values() {
+        let px = |alpha, red, green, blue| PixelF32 { alpha, red, green, blue };
+        let src = px(1.0, 0.8, 0.25, 0.0);
+        let dst = px(1.0, 0.5, 0.5, 1.0);
+        let cases = [
+            (TransferMode::Copy,       px(1.0, 0.8, 0.25, 0.0)),
+            (TransferMode::InFront,    px(1.0, 0.8, 0.25, 0.0)),
+            (TransferMode::Behind,     px(1.0, 0.5, 0.5, 1.0)),
+            (TransferMode::Add,        px(1.0, 1.3, 0.75, 1.0)),
+            (TransferMode::Mulitply,   px(1.0, 0.4, 0.125, 0.0)),
+            (TransferMode::Screen,     px(1.0, 0.9, 0.625, 1.0)),
+            (TransferMode::Overlay,    px(1.0, 0.8, 0.25, 1.0)),
+            (TransferMode::HardLight,  px(1.0, 0.8, 0.25, 0.0)),
+            (TransferMode::Darken,     px(1.0, 0.5, 0.25, 0.0)),
+            (TransferMode::Lighten,    px(1.0, 0.8, 0.5, 1.0)),
+            (TransferMode::Difference, px(1.0, 0.3, 0.25, 1.0)),
+            (TransferMode::Exclusion,  px(1.0, 0.5, 0.5, 1.0)),
+            (TransferMode::Subtract,   px(1.0, 0.0, 0.25, 1.0)),
+            (TransferMode::LinearBurn, px(1.0, 0.3, 0.0, 0.0)),
+        ];
+        for (mode, expected) in cases {
+            let out = mode.combiner32().unwrap()(src, dst);
+            let close = |a: f32, b: f32| (a - b).abs() < 1e-5;
+            assert!(close(out.alpha, expected.alpha) && close(out.red, expected.red) && close(out.green, expected.green) && close(out.blue, expected.blue),
+                "{mode:?}: {out:?} != {expected:?}");
+        }
+
+        // A half transparent source over an opaque destination keeps half of the destination.
+        let out = TransferMode::Mulitply.combiner32().unwrap()(px(0.5, 0.5, 0.0, 0.0), dst);
+        assert!((out.red - 0.5).abs() < 1e-5 && (out.blue - 0.5).abs() < 1e-5 && out.alpha == 1.0);
+        assert!(TransferMode::Dissolve.combiner32().is_none());
+    }
+
+    #[test]
+    fn draw_line8_clips_to_layer() {
+        let mut data = pixels(4, 4, 0);
+        let mut layer = layer8(4, 4, &mut data);
+        let white = Pixel8 { alpha: 255, red: 255, green: 255, blue: 255 };
+        layer.draw_line8(Point { h: -2, v: -2 }, Point { h: 5, v: 5 }, white, 1.0, false);
+        let alpha: Vec<u8> = data.iter().map(|p| p.alpha).collect();
+        assert_eq!(alpha, [255, 0, 0, 0, 0, 255, 0, 0, 0, 0, 255, 0, 0, 0, 0, 255]);
+
+        // Extreme endpoints neither overflow nor walk the whole segment.
+        let mut data = pixels(4, 4, 0);
+        let mut layer = layer8(4, 4, &mut data);
+        layer.draw_line8(Point { h: i32::MIN, v: i32::MIN }, Point { h: i32::MAX, v: i32::MAX }, white, 1.0, false);
+        layer.draw_line8(Point { h: i32::MIN, v: 100 }, Point { h: i32::MAX, v: 100 }, white, 1.0, true);
+        layer.draw_line8(Point { h: i32::MAX, v: i32::MIN }, Point { h: i32::MIN, v: i32::MAX }, white, 0.0, true);
+        let alpha: Vec<u8> = data.iter().map(|p| p.alpha).collect();
+        assert_eq!(alpha, [255, 0, 0, 0, 0, 255, 0, 0, 0, 0, 255, 0, 0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn tile_from_wraps_with_offset() {
+        let mut src_data = pixels(2, 2, 255);
+        for (i, p) in src_data.iter_mut().enumerate() {
+            p.red = i as u8;
+        }
+        let mut dst_data = pixels(5, 3, 0);
+        let src = layer8(2, 2, &mut src_data);
+        let mut dst = layer8(5, 3, &mut dst_data);
+        dst.tile_from(&src, Point { h: 1, v: -1 }).unwrap();
+        let red: Vec<u8> = dst_data.iter().map(|p| p.red).collect();
+        assert_eq!(red, [3, 2, 3, 2, 3, 1, 0, 1, 0, 1, 3, 2, 3, 2, 3]);
+    }
+
+    #[test]
+    fn apply_mask_honors_offset() {
+        let mut data = vec![Pixel8 { alpha: 255, red: 200, green: 200, blue: 200 }; 3];
+        let mut mask_data = vec![Pixel8 { alpha: 255, red: 0, green: 0, blue: 0 }, Pixel8 { alpha: 0, red: 0, green: 0, blue: 0 }];
+        let mut layer = layer8(3, 1, &mut data);
+        let mask = MaskWorld {
+            mask: *layer8(2, 1, &mut mask_data).layer,
+            offset: Point { h: 1, v: 0 },
+            what_is_mask: MaskFlags::None,
+        };
+        layer.apply_mask(&mask).unwrap();
+        let alpha_red: Vec<(u8, u8)> = data.iter().map(|p| (p.alpha, p.red)).collect();
+        assert_eq!(alpha_red, [(0, 0), (255, 200), (0, 0)]);
+    }
+
+    #[test]
+    fn rotate_90_clockwise() {
+        let mut src_data = pixels(3, 2, 255);
+        for (i, p) in src_data.iter_mut().enumerate() {
+            p.red = i as u8;
+        }
+        let mut dst_data = pixels(2, 3, 0);
+        let src = layer8(3, 2, &mut src_data);
+        let mut dst = layer8(2, 3, &mut dst_data);
+        src.rotate_90(&mut dst, 1).unwrap();
+        let red: Vec<u8> = dst_data.iter().map(|p| p.red).collect();
+        assert_eq!(red, [3, 0, 4, 1, 5, 2]);
+        assert!(src.rotate_90(&mut layer8(3, 2, &mut pixels(3, 2, 0)), 1).is_err());
+    }
+
+    #[test]
+    fn downsample_2x_averages_blocks() {
+        let mut src_data = pixels(3, 2, 255);
+        for (p, red) in src_data.iter_mut().zip([10, 20, 90, 30, 41, 255]) {
+            p.red = red;
+        }
+        let mut dst_data = pixels(2, 1, 0);
+        let src = layer8(3, 2, &mut src_data);
+        let mut dst = layer8(2, 1, &mut dst_data);
+        src.downsample_2x(&mut dst).unwrap();
+        let red: Vec<u8> = dst_data.iter().map(|p| p.red).collect();
+        assert_eq!(red, [25, 173]);
+        assert!(src.downsample_2x(&mut layer8(1, 1, &mut pixels(1, 1, 0))).is_err());
+    }
+
+    #[test]
+    fn fill_rect8_clips_to_layer() {
+        let mut data = pixels(3, 3, 0);
+        let mut layer = layer8(3, 3, &mut data);
+        let white = Pixel8 { alpha: 255, red: 255, green: 255, blue: 255 };
+        layer.fill_rect8(Rect { left: 1, top: -4, right: 9, bottom: 2 }, white);
+        layer.fill_rect8(Rect { left: 2, top: 2, right: 1, bottom: 3 }, white);
+        let alpha: Vec<u8> = data.iter().map(|p| p.alpha).collect();
+        assert_eq!(alpha, [0, 255, 255, 0, 255, 255, 0, 0, 0]);
+    }
+
+    #[test]
+    fn blocks_2x2_clamp_odd_sizes() {
+        let mut data = pixels(3, 3, 255);
+        for (i, p) in data.iter_mut().enumerate() {
+            p.red = i as u8;
+        }
+        let mut layer = layer8(3, 3, &mut data);
+        let reds: Vec<[u8; 4]> = layer.blocks_2x2_pixel8().unwrap().map(|b| b.map(|p| p.red)).collect();
+        assert_eq!(reds, [[0, 1, 3, 4], [2, 2, 5, 5], [6, 7, 6, 7], [8, 8, 8, 8]]);
+
+        let present: Vec<usize> = layer.blocks_2x2_pixel8_mut().unwrap().map(|b| b.iter().flatten().count()).collect();
+        assert_eq!(present, [4, 2, 2, 1]);
+
+        let mut deep = Layer::new_in_memory(3, 3, aegp::WorldType::U15).unwrap();
+        assert!(deep.blocks_2x2_pixel8().is_err());
+        assert!(deep.blocks_2x2_pixel8_mut().is_err());
+    }
+
+    #[test]
+    fn srgb_round_trips() {
+        let mut data = vec![Pixel8 { alpha: 255, red: 0, green: 10, blue: 188 }, Pixel8 { alpha: 255, red: 255, green: 128, blue: 1 }];
+        let mut layer = layer8(2, 1, &mut data);
+        layer.to_linear();
+        let linear: Vec<[u8; 4]> = data.iter().map(|p| [p.alpha, p.red, p.green, p.blue]).collect();
+        assert_eq!(linear, [[255, 0, 1, 128], [255, 255, 55, 0]]);
+
+        assert!((srgb_to_linear(0.5) - 0.214_041).abs() < 1e-5);
+        assert!((srgb_to_linear(0.02) - 0.02 / 12.92).abs() < 1e-7);
+        for v in [0.0, 0.001, 0.04045, 0.2, 0.5, 1.0] {
+            assert!((linear_to_srgb(srgb_to_linear(v)) - v).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn gaussian_blur_is_normalized_and_separable() {
+        let mut data = pixels(7, 3, 255);
+        data[7 + 3].red = 255;
+        let mut dst_data = pixels(7, 3, 0);
+        let src = layer8(7, 3, &mut data);
+        let mut dst = layer8(7, 3, &mut dst_data);
+        src.gaussian_blur_xy(1.0, 0.0, &mut dst).unwrap();
+        let red: Vec<u8> = dst_data.iter().map(|p| p.red).collect();
+        assert_eq!(red[7..14], [1, 14, 62, 102, 62, 14, 1]);
+        assert!(red[..7].iter().chain(&red[14..]).all(|r| *r == 0));
+        assert!(dst_data.iter().all(|p| p.alpha == 255));
+
+        let mut dst = layer8(7, 3, &mut dst_data);
+        assert!(src.gaussian_blur(-1.0, &mut dst).is_err());
+    }
+
+    #[test]
+    fn threshold_hard_and_soft() {
+        let mut data: Vec<Pixel8> = [0, 100, 127, 128, 200].iter().map(|&red| Pixel8 { alpha: 255, red, green: 0, blue: 0 }).collect();
+        let original = data.clone();
+        let mut layer = layer8(5, 1, &mut data);
+        layer.threshold(PixelChannel::Red, 0.5, false);
+        assert_eq!(data.iter().map(|p| p.alpha).collect::<Vec<_>>(), [0, 0, 0, 255, 255]);
+        assert!(data.iter().all(|p| p.red == p.alpha && p.blue == p.alpha));
+
+        data.copy_from_slice(&original);
+        let mut layer = layer8(5, 1, &mut data);
+        layer.soft_threshold(PixelChannel::Red, 0.5, 0.4, true);
+        assert_eq!(data.iter().map(|p| p.alpha).collect::<Vec<_>>(), [255, 196, 129, 126, 0]);
+    }
+
+    #[test]
+    fn erode_dilate_shapes() {
+        let mut data = pixels(5, 5, 0);
+        data[2 * 5 + 2].alpha = 255;
+        let mut dst_data = pixels(5, 5, 0);
+        let src = layer8(5, 5, &mut data);
+        let mut dst = layer8(5, 5, &mut dst_data);
+        src.dilate(1, &mut dst).unwrap();
+        let alpha: Vec<u8> = dst_data.iter().map(|p| p.alpha / 255).collect();
+        assert_eq!(alpha, [0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 1, 1, 1, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0]);
+
+        let mut dst = layer8(5, 5, &mut dst_data);
+        src.dilate_with(PixelChannel::Alpha, 1, StructuringElement::Square, &mut dst).unwrap();
+        assert_eq!(dst_data.iter().filter(|p| p.alpha == 255).count(), 9);
+
+        let mut eroded = pixels(5, 5, 0);
+        let mut dst = layer8(5, 5, &mut eroded);
+        layer8(5, 5, &mut dst_data).erode_with(PixelChannel::Alpha, 1, StructuringElement::Square, &mut dst).unwrap();
+        assert_eq!(eroded.iter().map(|p| p.alpha).collect::<Vec<_>>(), data.iter().map(|p| p.alpha).collect::<Vec<_>>());
+        assert!(src.erode_with(PixelChannel::Luminance, 1, StructuringElement::Square, &mut dst).is_err());
+    }
+
+    #[test]
+    fn composite_masked_uses_mask_per_pixel() {
+        let mut bg_data = vec![Pixel8 { alpha: 255, red: 0, green: 0, blue: 0 }; 3];
+        let mut fg_data = vec![Pixel8 { alpha: 255, red: 255, green: 255, blue: 255 }; 3];
+        let mut mask_data: Vec<Pixel8> = [0, 128, 255].iter().map(|&v| Pixel8 { alpha: 255, red: v, green: v, blue: v }).collect();
+        let mut bg = layer8(3, 1, &mut bg_data);
+        let fg = layer8(3, 1, &mut fg_data);
+        let mask = layer8(3, 1, &mut mask_data);
+        bg.composite_masked(&fg, &mask, PixelChannel::Luminance, TransferMode::Copy).unwrap();
+        assert_eq!(bg_data.iter().map(|p| p.red).collect::<Vec<_>>(), [0, 128, 255]);
+        assert!(bg_data.iter().all(|p| p.alpha == 255));
+
+        let small = layer8(2, 1, &mut mask_data);
+        assert!(bg.composite_masked(&fg, &small, PixelChannel::Alpha, TransferMode::Copy).is_err());
+    }
+
+    #[test]
+    fn add_noise_is_deterministic() {
+        let noisy = |seed: u64, monochrome: bool| {
+            let mut data = vec![Pixel8 { alpha: 255, red: 128, green: 128, blue: 128 }; 16];
+            layer8(4, 4, &mut data).add_noise(0.2, seed, monochrome);
+            data.iter().map(|p| [p.alpha, p.red, p.green, p.blue]).collect::<Vec<_>>()
+        };
+        assert_eq!(noisy(7, false), noisy(7, false));
+        assert_ne!(noisy(7, false), noisy(8, false));
+        assert!(noisy(7, true).iter().all(|[a, r, g, b]| *a == 255 && r == g && g == b));
+        assert!(noisy(7, false).iter().flatten().all(|v| (76..=180).contains(v) || *v == 255));
+
+        // Transparent premultiplied pixels stay transparent black.
+        let mut data = pixels(4, 1, 0);
+        layer8(4, 1, &mut data).add_noise(0.5, 7, false);
+        assert!(data.iter().all(|p| [p.alpha, p.red, p.green, p.blue] == [0; 4]));
+    }
+
+    #[test]
+    fn set_alpha_from_rescales_premultiplied_color() {
+        let mut data = vec![Pixel8 { alpha: 255, red: 200, green: 100, blue: 0 }, Pixel8 { alpha: 0, red: 0, green: 0, blue: 0 }];
+        let mut matte_data = vec![Pixel8 { alpha: 255, red: 128, green: 128, blue: 128 }; 2];
+        let mut layer = layer8(2, 1, &mut data);
+        let matte = layer8(2, 1, &mut matte_data);
+        layer.set_alpha_from(&matte, PixelChannel::Luminance).unwrap();
+        assert_eq!([data[0].alpha, data[0].red, data[0].green, data[0].blue], [128, 100, 50, 0]);
+        assert_eq!([data[1].alpha, data[1].red], [128, 0]);
+    }
+
+    #[test]
+    fn checkerboard_alternates_cells() {
+        let white = PixelF32 { alpha: 1.0, red: 1.0, green: 1.0, blue: 1.0 };
+        let black = PixelF32 { alpha: 1.0, red: 0.0, green: 0.0, blue: 0.0 };
+        for world_type in [aegp::WorldType::U8, aegp::WorldType::U15, aegp::WorldType::F32] {
+            let layer = Layer::checkerboard(5, 3, 2, white, black, world_type).unwrap();
+            assert_eq!(layer.world_type(), world_type);
+            let reds: Vec<f32> = (0..3).flat_map(|y| (0..5).map(move |x| (x, y))).map(|(x, y)| layer.pixel_f32(x, y).red).collect();
+            assert_eq!(reds, [
+                1.0, 1.0, 0.0, 0.0, 1.0,
+                1.0, 1.0, 0.0, 0.0, 1.0,
+                0.0, 0.0, 1.0, 1.0, 0.0,
+            ]);
+        }
+        assert!(Layer::checkerboard(5, 3, 0, white, black, aegp::WorldType::U8).is_err());
+    }
+
+    #[test]
+    fn is_identical_to_ignores_padding() {
+        let gray = PixelF32 { alpha: 1.0, red: 0.5, green: 0.5, blue: 0.5 };
+        // 3 pixels wide, so the in-memory world pads its rows to 16 bytes.
+        let a = Layer::solid(3, 2, gray, aegp::WorldType::U8).unwrap();
+        let mut data = vec![pixel_f32_to_8(gray); 6];
+        assert!(a.is_identical_to(&layer8(3, 2, &mut data)));
+
+        data[4].blue = 0;
+        assert!(!a.is_identical_to(&layer8(3, 2, &mut data)));
+        assert!(!a.is_identical_to(&Layer::solid(3, 2, gray, aegp::WorldType::U15).unwrap()));
+        assert!(!a.is_identical_to(&Layer::solid(2, 3, gray, aegp::WorldType::U8).unwrap()));
+    }
+
+    #[test]
+    fn mean_luma_averages_pixels() {
+        let mut data = pixels(2, 1, 255);
+        data[0] = Pixel8 { alpha: 255, red: 255, green: 255, blue: 255 };
+        let layer = layer8(2, 1, &mut data);
+        assert!((layer.mean_luma() - 0.5).abs() < 1e-6);
+
+        let green = Pixel8 { alpha: 255, red: 0, green: 255, blue: 0 };
+        assert!((green.luma() - 0.7152).abs() < 1e-6);
+        assert!((green.luma_rec601() - 0.587).abs() < 1e-6);
+    }
+
+    #[test]
+    fn color_ops_skip_transparent_premultiplied_pixels() {
+        let lifted = (0..8).map(|i| [0.5 + (i & 1) as f32 * 0.5, 0.5 + (i >> 1 & 1) as f32 * 0.5, 0.5 + (i >> 2) as f32 * 0.5]).collect();
+        let lut = Lut3d::new(2, lifted).unwrap();
+        let mut data = pixels(2, 1, 0);
+        data[1].alpha = 255;
+        let mut layer = layer8(2, 1, &mut data);
+        layer.apply_lut3d(&lut).unwrap();
+        layer.apply_levels(-0.5, 1.0, 1.0).unwrap();
+        assert_eq!([data[0].alpha, data[0].red, data[0].green, data[0].blue], [0; 4]);
+        assert_eq!([data[1].alpha, data[1].red], [255, 170]);
+    }
+
+    #[test]
+    fn apply_levels_remaps_and_clamps() {
+        let mut data = pixels(3, 1, 255);
+        data[0].red = 10;
+        data[1].red = 64;
+        data[2].red = 200;
+        let mut layer = layer8(3, 1, &mut data);
+        layer.apply_levels(0.0, 0.5, 1.0).unwrap();
+        drop(layer);
+        let red: Vec<u8> = data.iter().map(|p| p.red).collect();
+        assert_eq!(red, [20, 128, 255]);
+        assert!(data.iter().all(|p| p.alpha == 255));
+    }
+}
@@ -0,0 +1,155 @@
+use crate::*;
+
+/// A 3D color lookup table: an `N`x`N`x`N` cube of RGB values, sampled with tetrahedral interpolation.
+///
+/// Inputs are mapped from the `domain_min..domain_max` range (0..1 by default) onto the cube and clamped to its edges.
+/// Apply it to a layer with [`Layer::apply_lut3d()`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lut3d {
+    size: usize,
+    domain_min: [f32; 3],
+    domain_max: [f32; 3],
+    data: Vec<[f32; 3]>,
+}
+
+impl Lut3d {
+    /// Creates a LUT from `size`³ RGB entries, with red changing fastest and blue slowest (the order used by .cube files).
+    ///
+    /// Returns [`Error::InvalidParms`] if `size` is smaller than 2 or `data` doesn't have `size`³ entries.
+    pub fn new(size: usize, data: Vec<[f32; 3]>) -> Result<Self, Error> {
+        if size < 2 || size.checked_pow(3) != Some(data.len()) {
+            return Err(Error::InvalidParms);
+        }
+        Ok(Self { size, domain_min: [0.0; 3], domain_max: [1.0; 3], data })
+    }
+
+    /// Parses an Adobe/Resolve `.cube` file with a `LUT_3D_SIZE`, honoring `DOMAIN_MIN` and `DOMAIN_MAX`.
+    ///
+    /// Other keywords, like Resolve's `LUT_3D_INPUT_RANGE`, are skipped. Returns [`Error::InvalidParms`] for 1D LUTs and malformed files.
+    pub fn from_cube_str(s: &str) -> Result<Self, Error> {
+        let parse_rgb = |values: &[&str]| -> Result<[f32; 3], Error> {
+            match values {
+                [r, g, b] => Ok([
+                    r.parse().map_err(|_| Error::InvalidParms)?,
+                    g.parse().map_err(|_| Error::InvalidParms)?,
+                    b.parse().map_err(|_| Error::InvalidParms)?,
+                ]),
+                _ => Err(Error::InvalidParms),
+            }
+        };
+
+        let mut size = None;
+        let mut domain_min = [0.0; 3];
+        let mut domain_max = [1.0; 3];
+        let mut data = Vec::new();
+        for line in s.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with("TITLE") {
+                continue;
+            }
+            let mut words = line.split_whitespace();
+            let keyword = words.next().unwrap_or_default();
+            let values: Vec<&str> = words.collect();
+            match keyword {
+                "LUT_3D_SIZE" => size = Some(values.first().and_then(|v| v.parse::<usize>().ok()).ok_or(Error::InvalidParms)?),
+                "DOMAIN_MIN"  => domain_min = parse_rgb(&values)?,
+                "DOMAIN_MAX"  => domain_max = parse_rgb(&values)?,
+                "LUT_1D_SIZE" => return Err(Error::InvalidParms),
+                // Data rows start with a number, anything else is a keyword we don't use.
+                _ if keyword.parse::<f32>().is_err() => continue,
+                _ => {
+                    let mut rgb = vec![keyword];
+                    rgb.extend(values);
+                    data.push(parse_rgb(&rgb)?);
+                }
+            }
+        }
+        if (0..3).any(|i| domain_max[i] <= domain_min[i]) {
+            return Err(Error::InvalidParms);
+        }
+        let mut lut = Self::new(size.ok_or(Error::InvalidParms)?, data)?;
+        lut.domain_min = domain_min;
+        lut.domain_max = domain_max;
+        Ok(lut)
+    }
+
+    /// Returns the number of entries along each axis of the cube.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Looks up `rgb` with tetrahedral interpolation between the four surrounding entries.
+    pub fn sample(&self, rgb: [f32; 3]) -> [f32; 3] {
+        let max = (self.size - 1) as f32;
+        let mut base = [0usize; 3];
+        let mut frac = [0f32; 3];
+        for i in 0..3 {
+            let v = ((rgb[i] - self.domain_min[i]) / (self.domain_max[i] - self.domain_min[i]) * max).clamp(0.0, max);
+            // NaN clamps to NaN, treat it like 0.
+            let v = if v.is_nan() { 0.0 } else { v };
+            base[i] = (v as usize).min(self.size - 2);
+            frac[i] = v - base[i] as f32;
+        }
+        let entry = |r: usize, g: usize, b: usize| self.data[(base[0] + r) + (base[1] + g) * self.size + (base[2] + b) * self.size * self.size];
+        let [fr, fg, fb] = frac;
+
+        // Each case picks the tetrahedron containing the point and weights its four corners.
+        let corners = if fr > fg {
+            if fg > fb {
+                [(1.0 - fr, entry(0, 0, 0)), (fr - fg, entry(1, 0, 0)), (fg - fb, entry(1, 1, 0)), (fb, entry(1, 1, 1))]
+            } else if fr > fb {
+                [(1.0 - fr, entry(0, 0, 0)), (fr - fb, entry(1, 0, 0)), (fb - fg, entry(1, 0, 1)), (fg, entry(1, 1, 1))]
+            } else {
+                [(1.0 - fb, entry(0, 0, 0)), (fb - fr, entry(0, 0, 1)), (fr - fg, entry(1, 0, 1)), (fg, entry(1, 1, 1))]
+            }
+        } else if fb > fg {
+            [(1.0 - fb, entry(0, 0, 0)), (fb - fg, entry(0, 0, 1)), (fg - fr, entry(0, 1, 1)), (fr, entry(1, 1, 1))]
+        } else if fb > fr {
+            [(1.0 - fg, entry(0, 0, 0)), (fg - fb, entry(0, 1, 0)), (fb - fr, entry(0, 1, 1)), (fr, entry(1, 1, 1))]
+        } else {
+            [(1.0 - fg, entry(0, 0, 0)), (fg - fr, entry(0, 1, 0)), (fr - fb, entry(1, 1, 0)), (fb, entry(1, 1, 1))]
+        };
+        let mut out = [0.0; 3];
+        for (weight, c) in corners {
+            for i in 0..3 {
+                out[i] += weight * c[i];
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IDENTITY_2: &str = "TITLE \"identity\"\n# comment\nLUT_3D_SIZE 2\n\n\
+        0 0 0\n1 0 0\n0 1 0\n1 1 0\n0 0 1\n1 0 1\n0 1 1\n1 1 1\n";
+
+    #[test]
+    fn identity_cube_round_trips() {
+        let lut = Lut3d::from_cube_str(IDENTITY_2).unwrap();
+        assert_eq!(lut.size(), 2);
+        for rgb in [[0.0, 0.0, 0.0], [0.25, 0.5, 0.75], [0.9, 0.1, 0.4], [1.0, 1.0, 1.0]] {
+            let out = lut.sample(rgb);
+            assert!((0..3).all(|i| (out[i] - rgb[i]).abs() < 1e-6), "{rgb:?} -> {out:?}");
+        }
+        // Out of range inputs clamp to the edges of the cube.
+        assert_eq!(lut.sample([-1.0, 2.0, 0.0]), [0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn skips_unknown_keywords() {
+        let resolve = IDENTITY_2.replace("LUT_3D_SIZE 2\n", "LUT_3D_SIZE 2\nLUT_3D_INPUT_RANGE 0.0 1.0\n");
+        assert!(resolve.contains("LUT_3D_INPUT_RANGE"));
+        let lut = Lut3d::from_cube_str(&resolve).unwrap();
+        assert_eq!(lut.sample([0.25, 0.5, 0.75]), Lut3d::from_cube_str(IDENTITY_2).unwrap().sample([0.25, 0.5, 0.75]));
+    }
+
+    #[test]
+    fn rejects_malformed_cubes() {
+        assert!(Lut3d::from_cube_str("LUT_3D_SIZE 2\n0 0 0\n").is_err());
+        assert!(Lut3d::from_cube_str("LUT_1D_SIZE 2\n0 0 0\n1 1 1\n").is_err());
+        assert!(Lut3d::from_cube_str(&IDENTITY_2.replace("1 1 1", "1 x 1")).is_err());
+    }
+}
@@ -11,6 +11,8 @@ mod gpu;        pub use gpu::*;
 mod handles;    pub use handles::*;
 mod in_data;    pub use in_data::*;
 mod layer;      pub use layer::*;
+mod layer_ops;
+mod lut;        pub use lut::*;
 mod out_data;   pub use out_data::*;
 mod parameters; pub use parameters::*;
 mod pixel;      pub use pixel::*;
@@ -19,6 +21,8 @@ mod effect;     pub use effect::*;
 mod interact_callbacks;    pub use interact_callbacks::*;
 mod util_callbacks;        pub use util_callbacks::*;
 mod external_dependencies; pub use external_dependencies::*;
+mod frame_cache;           pub use frame_cache::*;
+mod frame_ring_buffer;     pub use frame_ring_buffer::*;
 
 pub mod suites {
     pub(crate) mod adv_item;              pub use adv_item            ::AdvItemSuite               as AdvItem;
@@ -60,6 +64,7 @@ pub use suites::app::{
     AppColorType,
     AppPersonalTextInfo,
     AppProgressDialog,
+    BusyCursor,
     CursorType,
     EyeDropperSampleMode,
     FontStyleSheet,
@@ -186,7 +191,7 @@ impl From<CompositeMode> for ae_sys::PF_CompositeMode {
 
 define_struct! {
     ae_sys::PF_Point,
-    #[derive(Eq)]
+    #[derive(Eq, serde::Serialize, serde::Deserialize)]
     Point {
         h: i32,
         v: i32,
@@ -200,7 +205,7 @@ impl Point {
 
 define_struct! {
     ae_sys::PF_RationalScale,
-    #[derive(Eq)]
+    #[derive(Eq, serde::Serialize, serde::Deserialize)]
     RationalScale {
         num: i32,
         den: u32,
@@ -315,6 +320,15 @@ define_enum! {
 
 define_handle_wrapper!(EffectBlendingTables, PF_EffectBlendingTables);
 
+impl EffectBlendingTables {
+    /// Retrieves the blending tables for the current working color space, via [`aegp::suites::ColorSettings::blending_tables()`].
+    ///
+    /// Pass them to [`aegp::suites::Composite::transfer_rect()`] to reuse them across repeated composites with the same mode.
+    pub fn new(render_context: crate::pr::RenderContextHandle) -> Result<Self, Error> {
+        Ok(Self(crate::aegp::suites::ColorSettings::new()?.blending_tables(render_context)?))
+    }
+}
+
 define_enum! {
     ae_sys::PF_ParamIndex,
     ParamIndex {
@@ -400,6 +414,9 @@ impl Fixed {
     pub fn as_f32(&self) -> f32 {
         self.0 as f32 / 65536.0
     }
+    pub fn as_f64(&self) -> f64 {
+        self.0 as f64 / 65536.0
+    }
 
     pub fn as_fixed(&self) -> ae_sys::PF_Fixed {
         self.0
@@ -60,18 +60,28 @@ impl OutData {
     pub fn set_height(&mut self, height: u32) {
         self.as_mut().height = height as ae_sys::A_long;
     }
+    pub fn dimensions(&self) -> (u32, u32) {
+        (self.width(), self.height())
+    }
+    /// Sets the size of the output buffer, e.g. for generators whose output doesn't depend on the input layer or effects that grow the layer.
+    ///
+    /// After Effects reads it after `FRAME_SETUP`. To keep the content in place, shift it with [`set_origin()`](Self::set_origin) by
+    /// the amount the output grew towards the top left.
+    pub fn set_dimensions(&mut self, width: u32, height: u32) {
+        self.set_width(width);
+        self.set_height(height);
+    }
     pub fn origin(&self) -> Point {
         self.as_ref().origin.into()
     }
     pub fn set_origin(&mut self, origin: Point) {
         self.as_mut().origin = origin.into();
     }
+    /// Sets the message After Effects displays after the current command, e.g. the text of the About box.
+    ///
+    /// Messages longer than the 255 bytes that fit into `return_msg` are truncated at a character boundary.
     pub fn set_return_msg(&mut self, msg: &str) {
-        //let buf = std::ffi::CString::new(s).unwrap().into_bytes_with_nul();
-        //self.return_msg[0..buf.len()].copy_from_slice(unsafe { std::mem::transmute(buf.as_slice()) });
-        let msg = msg.as_bytes();
-        assert!(msg.len() < 256);
-        self.as_mut().return_msg[..msg.len()].copy_from_slice(unsafe { std::mem::transmute(msg) });
+        crate::copy_to_c_buffer(&mut self.as_mut().return_msg, msg);
     }
     pub fn set_error_msg(&mut self, msg: &str) {
         self.set_return_msg(msg);
@@ -80,6 +90,9 @@ impl OutData {
     pub fn set_version(&mut self, v: u32) {
         self.as_mut().my_version = v as ae_sys::A_u_long;
     }
+    pub fn version(&self) -> u32 {
+        self.as_ref().my_version
+    }
     pub fn set_out_flags(&mut self, v: OutFlags) {
         self.as_mut().out_flags = v.into();
     }
@@ -100,6 +113,16 @@ impl OutData {
             self.as_mut().out_flags2 &= !(Into::<ae_sys::PF_OutFlags2>::into(flag));
         }
     }
+    /// Returns `true` if `flag` is currently set in `out_flags`.
+    pub fn out_flag(&self, flag: OutFlags) -> bool {
+        let flag: ae_sys::PF_OutFlags = flag.into();
+        self.as_ref().out_flags & flag == flag
+    }
+    /// Returns `true` if `flag` is currently set in `out_flags2`.
+    pub fn out_flag2(&self, flag: OutFlags2) -> bool {
+        let flag: ae_sys::PF_OutFlags2 = flag.into();
+        self.as_ref().out_flags2 & flag == flag
+    }
     pub fn set_force_rerender(&mut self) {
         self.set_out_flag(OutFlags::ForceRerender, true);
     }
@@ -134,15 +134,23 @@ define_param_wrapper! {
     Param::Angle,
     AngleDef { },
     impl value: Fixed,
+    impl valid_min: Fixed,
+    impl valid_max: Fixed,
 }
 impl AngleDef<'_> {
+    /// Sets the default angle in degrees. Like `PF_ADD_ANGLE`, this also initializes the current value.
     pub fn set_default(&mut self, v: f32) -> &mut Self {
         self.def.dephault = Fixed::from(v).as_fixed();
+        self.def.value = self.def.dephault;
         self
     }
     pub fn default(&self) -> f32 {
         Fixed::from_fixed(self.def.dephault).into()
     }
+    /// Returns the current value in degrees, with the full precision of the underlying fixed point value.
+    pub fn degrees(&self) -> f64 {
+        Fixed::from_fixed(self.def.value).as_f64()
+    }
     pub fn float_value(&self) -> Result<f64, Error> {
         if self._in_data.is_null() || self._parent_ptr.is_none() {
             return Err(Error::InvalidParms);
@@ -174,8 +182,11 @@ define_param_wrapper! {
     impl value: bool,
 }
 impl<'a> CheckBoxDef<'_> {
+    /// Sets the default state. Like `PF_ADD_CHECKBOX`, this also initializes the current value,
+    /// so call [`set_value()`](Self::set_value) afterwards if the two should differ.
     pub fn set_default(&mut self, v: bool) -> &mut Self {
         self.def.dephault = if v { 1 } else { 0 };
+        self.def.value = self.def.dephault as _;
         self
     }
     pub fn default(&self) -> bool {
@@ -201,6 +212,18 @@ define_param_wrapper! {
     impl default: Pixel8,
 }
 impl ColorDef<'_> {
+    /// Sets the default color from a float pixel.
+    ///
+    /// The param only stores 8-bit colors, so `px` is clamped to 0..1 and rounded. In 16-bit and 32-bit projects
+    /// After Effects still shows the 8-bit color widget, but the color picker can pick values outside of 0..1, which are only
+    /// visible through [`float_value()`](Self::float_value). Read the value with that instead of [`value()`](Self::value) to avoid clipping.
+    pub fn set_default_f32(&mut self, px: PixelF32) -> &mut Self {
+        self.set_default(pixel_f32_to_8(px))
+    }
+
+    /// Returns the full precision value of the color, including values outside of 0..1 in float projects.
+    ///
+    /// Only available for params checked out from the host, otherwise [`Error::InvalidParms`] is returned.
     pub fn float_value(&self) -> Result<PixelF32, Error> {
         if self._in_data.is_null() || self._parent_ptr.is_none() {
             return Err(Error::InvalidParms);
@@ -390,10 +413,24 @@ define_param_wrapper! {
     impl value: i32,
     impl default: i32,
 }
+/// Builds the `"list|of|choices"` string Ae expects for popup params.
+///
+/// Panics if a name contains a nul byte.
+pub fn join_popup_names(names: impl IntoIterator<Item = impl AsRef<str>>) -> CString {
+    let joined = names.into_iter().fold(String::new(), |mut acc, name| {
+        if !acc.is_empty() {
+            acc.push('|');
+        }
+        acc.push_str(name.as_ref());
+        acc
+    });
+    CString::new(joined).unwrap()
+}
+
 impl<'a> PopupDef<'a> {
-    pub fn set_options(&mut self, options: &[&str]) {
-        // Build a string in the format "list|of|choices|", the format Ae expects.
-        self.options = CString::new(options.join("|")).unwrap();
+    pub fn set_options(&mut self, options: impl IntoIterator<Item = impl AsRef<str>>) {
+        let options: Vec<_> = options.into_iter().collect();
+        self.options = join_popup_names(&options);
         self.def.u.namesptr = self.options.as_ptr();
         self.def.num_choices = options.len().try_into().unwrap();
     }
@@ -414,6 +451,14 @@ impl<'a> LayerDef<'a> {
     pub fn set_default_to_this_layer(&mut self) {
         self.def.dephault = ae_sys::PF_LayerDefault_MYSELF;
     }
+    /// Makes the param default to no layer ("None" in the popup), which is also the default of a new `LayerDef`.
+    pub fn set_default_to_none(&mut self) {
+        self.def.dephault = ae_sys::PF_LayerDefault_NONE;
+    }
+    /// Returns whether the param defaults to the layer the effect is applied to.
+    pub fn default_is_this_layer(&self) -> bool {
+        self.def.dephault == ae_sys::PF_LayerDefault_MYSELF
+    }
     pub fn value(&self) -> Option<Layer> {
         if self.def.data.is_null() {
             None
@@ -443,15 +488,34 @@ impl<'p> Into<Param<'p>> for NullDef<'p> {
 define_param_wrapper! {
     PF_Param_ARBITRARY_DATA, PF_ArbitraryDef, arb_d,
     Param::Arbitrary,
-    ArbitraryDef { },
+    ArbitraryDef {
+        // A `Cell` so that `ParamDef::set_param` can take over the handle through a shared reference.
+        dispose_default: std::cell::Cell<Option<fn(ae_sys::PF_Handle)>>,
+    },
     impl pad: i16,
 }
 impl ArbitraryDef<'_> {
+    /// Sets the default value, stored in a new handle.
+    ///
+    /// [`ParamDef::set_param`] takes over the handle, and hands it to the host once [`ParamDef::add`] succeeds. If the def is dropped
+    /// before that, e.g. because of an error during `PARAMS_SETUP`, the handle is disposed instead of leaked.
     pub fn set_default<T>(&mut self, value: T) -> Result<&mut Self, Error> {
-        self.def.dephault = Handle::into_raw(Handle::new(value)?);
+        let handle = Handle::into_raw(Handle::new(value)?);
+        self.dispose_owned_default();
+        self.def.dephault = handle;
+        self.dispose_default.set(Some(|handle| { let _ = Handle::<T>::from_raw(handle, true); }));
         Ok(self)
     }
 
+    fn dispose_owned_default(&mut self) {
+        if let Some(dispose) = self.dispose_default.take() {
+            if !self.def.dephault.is_null() {
+                dispose(self.def.dephault);
+                self.def.dephault = std::ptr::null_mut();
+            }
+        }
+    }
+
     pub fn set_value<T>(&mut self, value: T) -> Result<&mut Self, Error> {
         if !self.def.value.is_null() {
             let _ = Handle::<T>::from_raw(self.def.value, true);
@@ -472,6 +536,11 @@ impl ArbitraryDef<'_> {
         self
     }
 }
+impl Drop for ArbitraryDef<'_> {
+    fn drop(&mut self) {
+        self.dispose_owned_default();
+    }
+}
 // ―――――――――――――――――――――――――――――――――― Arbitrary ―――――――――――――――――――――――――――――――――――
 
 pub trait ArbitraryData<T> {
@@ -795,12 +864,85 @@ impl Debug for Param<'_> {
     }
 }
 
-#[derive(Clone)]
+/// [`ParamFlag`] and [`ParamUIFlags`] for a param, checked against the param type before they are applied.
+///
+/// ```ignore
+/// params.add_customized(Params::Mode, "Mode", PopupDef::setup(|d| { ... }), |def| {
+///     def.apply_config(ParamConfig::new().flags(ParamFlag::SUPERVISE).ui_flags(ParamUIFlags::RADIO_BUTTON)).unwrap();
+///     -1
+/// })?;
+/// ```
+///
+/// Setting [`ParamFlag::SUPERVISE`] means the effect has to handle [`Command::UserChangedParam`] for this param,
+/// which can't be checked here.
+pub struct ParamConfig {
+    flags: ParamFlag,
+    ui_flags: ParamUIFlags,
+}
+
+impl Default for ParamConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParamConfig {
+    pub fn new() -> Self {
+        Self {
+            flags: ParamFlag::empty(),
+            ui_flags: ParamUIFlags::empty(),
+        }
+    }
+    /// Adds `flags` to the param flags.
+    pub fn flags(mut self, flags: ParamFlag) -> Self {
+        self.flags |= flags;
+        self
+    }
+    /// Adds `ui_flags` to the UI flags.
+    pub fn ui_flags(mut self, ui_flags: ParamUIFlags) -> Self {
+        self.ui_flags |= ui_flags;
+        self
+    }
+
+    /// Checks the flags against `param_type`, returning [`Error::InvalidParms`] for a combination the host doesn't support:
+    ///
+    /// * [`ParamFlag::USE_VALUE_FOR_OLD_PROJECTS`] on a layer param.
+    /// * [`ParamFlag::LAYER_PARAM_IS_TRACKMATTE`] on anything but a layer param.
+    /// * [`ParamUIFlags::RADIO_BUTTON`] on anything but a popup.
+    /// * [`ParamUIFlags::CONTROL_ONLY`] on a param without a standard control (layer, arbitrary data, custom), or together
+    ///   with custom UI ([`ParamUIFlags::TOPIC`] or [`ParamUIFlags::CONTROL`]).
+    pub fn validate(&self, param_type: ParamType) -> Result<(), Error> {
+        let has = |flag: ParamFlag| self.flags.bits() & flag.bits() != 0;
+        let has_ui = |flag: ParamUIFlags| self.ui_flags.bits() & flag.bits() != 0;
+        let is_layer = param_type == ParamType::Layer;
+
+        let invalid = (has(ParamFlag::USE_VALUE_FOR_OLD_PROJECTS) && is_layer)
+            || (has(ParamFlag::LAYER_PARAM_IS_TRACKMATTE) && !is_layer)
+            || (has_ui(ParamUIFlags::RADIO_BUTTON) && param_type != ParamType::PopUp)
+            || (has_ui(ParamUIFlags::CONTROL_ONLY)
+                && (matches!(param_type, ParamType::Layer | ParamType::ArbitraryData | ParamType::Custom)
+                    || has_ui(ParamUIFlags::TOPIC)
+                    || has_ui(ParamUIFlags::CONTROL)));
+        if invalid {
+            log::error!("Invalid flags for {param_type:?} param: {:#x}, ui flags {:#x}", self.flags.bits(), self.ui_flags.bits());
+            return Err(Error::InvalidParms);
+        }
+        Ok(())
+    }
+}
+
+fn c_buffer_to_string(buffer: &[std::ffi::c_char]) -> String {
+    let bytes: Vec<u8> = buffer.iter().take_while(|&&c| c != 0).map(|&c| c as u8).collect();
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
 pub struct ParamDef<'p> {
     param_def: Ownership<'p, ae_sys::PF_ParamDef>,
     checkin_on_drop: bool,
     index: Option<i32>,
     in_data: InData,
+    /// Default handle of an arbitrary param taken over in `set_param()`, disposed on drop unless `add()` handed it to the host.
+    owned_default: Option<(ae_sys::PF_Handle, fn(ae_sys::PF_Handle))>,
 }
 
 impl<'p> ParamDef<'p> {
@@ -810,6 +952,7 @@ impl<'p> ParamDef<'p> {
             checkin_on_drop: false,
             in_data,
             index: None,
+            owned_default: None,
         }
     }
 
@@ -842,11 +985,14 @@ impl<'p> ParamDef<'p> {
             checkin_on_drop: false,
             in_data,
             index,
+            owned_default: None,
         }
     }
 
+    /// Adds the param to the effect. From then on the host owns the default handle of an arbitrary param set with [`set_param()`](Self::set_param).
     pub fn add(&mut self, index: i32) -> Result<(), Error> {
         self.in_data.interact().add_param(index, &*self.param_def)?;
+        self.owned_default = None;
         if index != -1 {
             self.index = Some(index);
         }
@@ -859,20 +1005,57 @@ impl<'p> ParamDef<'p> {
             return Err(Error::InvalidIndex);
         }
 
-        // For some reason the checked out param_type is 0 so we need to override using the info we have from params map.
-        if index > 0 && expected_type.is_some() {
-            param_def.param_type = expected_type.unwrap().into();
-        }
+        apply_expected_type(&mut param_def, index, expected_type);
 
         Ok(Self {
             param_def: Ownership::Rust(param_def),
             checkin_on_drop: true,
             in_data,
             index: Some(index),
+            owned_default: None,
         })
     }
 
+    /// Checks out the layer param at `index` `frame_offset` frames away from the current time, e.g. `-1` for the previous frame.
+    ///
+    /// The time is `current_time + frame_offset * time_step` in the time scale of `in_data`. Returns [`Error::InvalidParms`] if that overflows.
+    pub fn checkout_layer_at_offset(in_data: InData, index: i32, frame_offset: i32) -> Result<Self, Error> {
+        let what_time = frame_offset.checked_mul(in_data.time_step())
+            .and_then(|offset| in_data.current_time().checked_add(offset))
+            .ok_or(Error::InvalidParms)?;
+        Self::checkout(in_data, index, what_time, in_data.time_step(), in_data.time_scale(), Some(ParamType::Layer))
+    }
+
+    /// Checks out params `0..types.len()` at the given time in one go, where `types[i]` is the type of param `i`.
+    ///
+    /// The host reports checked out params as layers, so the types are needed to read the others correctly; index 0 is
+    /// always the input layer and its type is ignored. [`Parameters::checkout_all`] fills them in from the param map.
+    /// All of them are checked back in when the returned [`CheckedOutParams`] is dropped, including on early return.
+    /// If any checkout fails, the params checked out so far are checked in before the error is returned.
+    pub fn checkout_all(in_data: InData, types: &[Option<ParamType>], what_time: i32, time_step: i32, time_scale: u32) -> Result<CheckedOutParams<'p>, Error> {
+        let params = types.iter().enumerate()
+            .map(|(index, type_)| Self::checkout(in_data, index as i32, what_time, time_step, time_scale, *type_))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(CheckedOutParams { params })
+    }
+
+    /// Checks in a param obtained with [`checkout()`](Self::checkout) right away, returning any error from the host.
+    ///
+    /// Dropping a checked out param checks it in as well, but errors can only be logged there.
+    pub fn checkin_now(mut self) -> Result<(), Error> {
+        if !self.checkin_on_drop {
+            return Ok(());
+        }
+        self.checkin_on_drop = false;
+        self.in_data.interact().checkin_param(&self.param_def)
+    }
+
+    /// Copies `param` into this def. `param` must outlive the call to [`add()`](Self::add), as the def points to its strings.
+    ///
+    /// The default handle of an [`ArbitraryDef`] moves to this def, so it's disposed exactly once: by the host after [`add()`](Self::add),
+    /// or by this def when it's dropped without being added.
     pub fn set_param(&mut self, param: &Param) {
+        self.dispose_owned_default();
         match param {
             Param::Popup(pd) => {
                 self.param_def.u.pd = *pd.def;
@@ -917,6 +1100,9 @@ impl<'p> ParamDef<'p> {
             Param::Arbitrary(arb_d) => {
                 self.param_def.u.arb_d = *arb_d.def;
                 self.param_def.param_type = ae_sys::PF_Param_ARBITRARY_DATA;
+                if let Some(dispose) = arb_d.dispose_default.take() {
+                    self.owned_default = Some((arb_d.def.dephault, dispose));
+                }
             }
             Param::Layer(ld) => {
                 self.param_def.u.ld = *ld.def;
@@ -1023,11 +1209,27 @@ impl<'p> ParamDef<'p> {
         &mut self.param_def.u.ld
     }
 
+    /// Returns the param name, up to the first nul. Invalid UTF-8 is replaced.
+    pub fn name(&self) -> String {
+        c_buffer_to_string(&self.param_def.name)
+    }
+
+    /// Returns the value string the host displays for slider and fixed slider params, or `None` for other param types.
+    pub fn value_str(&self) -> Option<String> {
+        unsafe {
+            match self.param_def.param_type {
+                ae_sys::PF_Param_SLIDER     => Some(c_buffer_to_string(&self.param_def.u.sd.value_str)),
+                ae_sys::PF_Param_FIX_SLIDER => Some(c_buffer_to_string(&self.param_def.u.fd.value_str)),
+                _ => None,
+            }
+        }
+    }
+
+    /// Sets the param name. Names longer than 31 bytes are truncated on a char boundary (and logged), since the host only has room for 31.
     pub fn set_name(&mut self, name: &str) {
-        assert!(name.len() < 32);
-        let name_cstr = CString::new(name).unwrap();
-        let name_slice = name_cstr.to_bytes_with_nul();
-        self.param_def.name[0..name_slice.len()].copy_from_slice(unsafe { std::mem::transmute(name_slice) });
+        if !crate::copy_to_c_buffer(&mut self.param_def.name, name) {
+            log::warn!("Param name {name:?} is longer than 31 bytes and was truncated");
+        }
     }
 
     pub fn set_flags       (&mut self, f: ParamFlag)    { self.param_def.flags           = f.bits() as _; }
@@ -1042,6 +1244,40 @@ impl<'p> ParamDef<'p> {
     pub fn change_flags(&self) -> ChangeFlag   {   ChangeFlag::from_bits_truncate(unsafe { self.param_def.uu.change_flags }) }
     pub fn ui_flags    (&self) -> ParamUIFlags { ParamUIFlags::from_bits_truncate(self.param_def.ui_flags) }
 
+    /// Validates `config` against the param type with [`ParamConfig::validate`], then sets both the flags and the UI flags.
+    pub fn apply_config(&mut self, config: ParamConfig) -> Result<(), Error> {
+        config.validate(self.param_type())?;
+        self.set_flags(config.flags);
+        self.set_ui_flags(config.ui_flags);
+        Ok(())
+    }
+
+    /// Greys out the param in the UI, or enables it again.
+    ///
+    /// For a param checked out from the host, e.g. while handling [`Command::UserChangedParam`], the UI is updated right away.
+    /// During param setup only the flag is set.
+    pub fn set_disabled(&mut self, disabled: bool) -> Result<(), Error> {
+        self.set_ui_flag_and_update(ParamUIFlags::DISABLED, disabled)
+    }
+
+    /// Hides or shows the param, see [`ParamUIFlags::INVISIBLE`] for where this is respected.
+    ///
+    /// For a param checked out from the host the UI is updated right away. During param setup only the flag is set.
+    pub fn set_invisible(&mut self, invisible: bool) -> Result<(), Error> {
+        self.set_ui_flag_and_update(ParamUIFlags::INVISIBLE, invisible)
+    }
+
+    fn set_ui_flag_and_update(&mut self, flag: ParamUIFlags, set: bool) -> Result<(), Error> {
+        if (self.ui_flags().bits() & flag.bits() == flag.bits()) == set {
+            return Ok(());
+        }
+        self.set_ui_flag(flag, set);
+        if self.index.is_some() {
+            self.update_param_ui()?;
+        }
+        Ok(())
+    }
+
     pub fn set_ui_width(&mut self, width: u16) {
         self.param_def.ui_width = width as _;
     }
@@ -1061,13 +1297,84 @@ impl<'p> ParamDef<'p> {
     }
 }
 
+impl Clone for ParamDef<'_> {
+    fn clone(&self) -> Self {
+        Self {
+            param_def: self.param_def.clone(),
+            checkin_on_drop: self.checkin_on_drop,
+            index: self.index,
+            in_data: self.in_data,
+            // The original keeps disposing the default handle, the clone only points at it.
+            owned_default: None,
+        }
+    }
+}
+
+impl ParamDef<'_> {
+    fn dispose_owned_default(&mut self) {
+        if let Some((handle, dispose)) = self.owned_default.take() {
+            if !handle.is_null() {
+                dispose(handle);
+            }
+        }
+    }
+}
+
 impl Drop for ParamDef<'_> {
     fn drop(&mut self) {
+        self.dispose_owned_default();
         if self.checkin_on_drop {
-            self.in_data.interact().checkin_param(&*self.param_def).unwrap()
+            if let Err(e) = self.in_data.interact().checkin_param(&*self.param_def) {
+                log::error!("Failed to check in param {:?}: {e}", self.index);
+            }
+        }
+    }
+}
+// For some reason the checked out param_type is 0 (a layer), so we need to override it using the info we have from the params map.
+fn apply_expected_type(param_def: &mut ae_sys::PF_ParamDef, index: i32, expected_type: Option<ParamType>) {
+    if let (true, Some(type_)) = (index > 0, expected_type) {
+        param_def.param_type = type_.into();
+    }
+}
+
+/// Params checked out with [`ParamDef::checkout_all`], indexed by param index. Checks all of them in on drop.
+pub struct CheckedOutParams<'p> {
+    params: Vec<ParamDef<'p>>,
+}
+
+impl CheckedOutParams<'_> {
+    /// Checks in all params right away, returning the first error from the host.
+    pub fn checkin_now(mut self) -> Result<(), Error> {
+        let mut result = Ok(());
+        for param in self.params.drain(..) {
+            let r = param.checkin_now();
+            if result.is_ok() {
+                result = r;
+            }
         }
+        result
+    }
+}
+
+impl<'p> std::ops::Deref for CheckedOutParams<'p> {
+    type Target = [ParamDef<'p>];
+    fn deref(&self) -> &Self::Target {
+        &self.params
+    }
+}
+impl std::ops::DerefMut for CheckedOutParams<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.params
+    }
+}
+
+impl Drop for CheckedOutParams<'_> {
+    fn drop(&mut self) {
+        // Each `ParamDef` checks itself in (and logs failures) when dropped.
+        self.params.clear();
     }
 }
+
 impl Debug for ParamDef<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ParamDef")
@@ -1149,13 +1456,22 @@ impl<'p, P: Eq + PartialEq + Hash + Copy + Debug> Parameters<'p, P> {
         hasher.finish() as i32
     }
 
+    /// Adds a group named `name` containing the params added by `inner_cb`, using `type_start` and `type_end` for its start and end.
     pub fn add_group<F: FnOnce(&mut Self)>(&mut self, type_start: P, type_end: P, name: &str, inner_cb: F) -> Result<(), Error> {
+        self.add_collapsible_group(type_start, type_end, name, false, inner_cb)
+    }
+
+    /// Same as [`add_group()`](Self::add_group), but the group is twirled up when the effect is first applied if `start_collapsed` is set.
+    pub fn add_collapsible_group<F: FnOnce(&mut Self)>(&mut self, type_start: P, type_end: P, name: &str, start_collapsed: bool, inner_cb: F) -> Result<(), Error> {
         assert!(!self.in_data.is_null());
 
         let mut param_def = ParamDef::new(InData::from_raw(self.in_data));
         param_def.set_name(name);
         param_def.as_mut().param_type = ParamType::GroupStart.into();
         param_def.set_id(Self::param_id(type_start));
+        if start_collapsed {
+            param_def.set_flags(ParamFlag::START_COLLAPSED);
+        }
         param_def.add(-1)?;
         self.map.insert(type_start, ParamMapInfo::new(self.num_params, ParamType::GroupStart));
         self.num_params += 1;
@@ -1228,6 +1544,22 @@ impl<'p, P: Eq + PartialEq + Hash + Copy + Debug> Parameters<'p, P> {
         Ok(())
     }
 
+    /// Adds an arbitrary data param with a custom UI of `ui_width`x`ui_height` in the Effect Controls window.
+    ///
+    /// The default value is `T::default()`, and [`ParamUIFlags::CONTROL`] is set so the host sends the UI events for the
+    /// param. The value callbacks are still handled with [`ArbParamsExtra::dispatch()`], and the custom UI has to be
+    /// registered with [`InteractCallbacks::register_ui()`].
+    pub fn add_arbitrary_with_ui<T: Default>(&mut self, type_: P, name: &str, ui_width: u16, ui_height: u16) -> Result<(), Error> {
+        let mut def = ArbitraryDef::new();
+        def.set_default(T::default())?;
+        self.add_customized(type_, name, def, |param| {
+            param.set_ui_flag(ParamUIFlags::CONTROL, true);
+            param.set_ui_width(ui_width);
+            param.set_ui_height(ui_height);
+            -1
+        })
+    }
+
     #[inline(always)]
     pub fn get(&self, type_: P) -> Result<Ownership<ParamDef<'p>>, Error> {
         self.get_at(type_, None, None, None)
@@ -1279,6 +1611,28 @@ impl<'p, P: Eq + PartialEq + Hash + Copy + Debug> Parameters<'p, P> {
         Ok(Ownership::Rust(param))
     }
 
+    /// Checks out all params at the current time with their types from the param map, see [`ParamDef::checkout_all`].
+    pub fn checkout_all(&self) -> Result<CheckedOutParams<'p>, Error> {
+        let mut types = vec![None; self.num_params];
+        for info in self.map.values() {
+            if let Some(type_) = types.get_mut(info.index) {
+                *type_ = Some(info.type_);
+            }
+        }
+        let in_data = self.in_data();
+        ParamDef::checkout_all(in_data, &types, in_data.current_time(), in_data.time_step(), in_data.time_scale())
+    }
+
+    /// Checks out a layer param `frame_offset` frames away from the current time, see [`ParamDef::checkout_layer_at_offset`].
+    pub fn checkout_layer_at_offset(&self, type_: P, frame_offset: i32) -> Result<ParamDef<'p>, Error> {
+        let index = self.index(type_).ok_or(Error::InvalidIndex)?;
+        let param = ParamDef::checkout_layer_at_offset(self.in_data(), index as i32, frame_offset)?;
+        if !param.is_valid() {
+            return Err(Error::InvalidParms);
+        }
+        Ok(param)
+    }
+
     pub fn num_params(&self) -> usize {
         self.num_params
     }
@@ -1297,6 +1651,16 @@ impl<'p, P: Eq + PartialEq + Hash + Copy + Debug> Parameters<'p, P> {
         self.map.get(&type_).map(|x| x.type_)
     }
 
+    /// Reads the current value of a param, converted to `T`. See [`ParamValue`] for the supported conversions.
+    pub fn value<T: ParamValue>(&self, type_: P) -> Result<T, Error> {
+        T::from_param(&*self.get(type_)?)
+    }
+
+    /// Reads all params into a user struct, see [`FromParams`].
+    pub fn read<T: FromParams<P>>(&self) -> Result<T, Error> {
+        T::from_params(self)
+    }
+
     pub fn cloned(&self) -> Parameters<'p, P> {
         Parameters::<'p, P> {
             in_data: self.in_data.clone(),
@@ -1306,3 +1670,115 @@ impl<'p, P: Eq + PartialEq + Hash + Copy + Debug> Parameters<'p, P> {
         }
     }
 }
+
+// ―――――――――――――――――――――――――――――――――― Typed values ――――――――――――――――――――――――――――――――――
+
+/// A plain value that can be read from a param, used by [`Parameters::value()`].
+///
+/// | Type         | Param types           |
+/// |--------------|-----------------------|
+/// | `bool`       | Checkbox              |
+/// | `i32`        | Slider, Popup         |
+/// | `f64`, `f32` | Float slider, Angle   |
+/// | `Pixel8`     | Color                 |
+/// | `(f32, f32)` | Point                 |
+pub trait ParamValue: Sized {
+    fn from_param(param: &ParamDef) -> Result<Self, Error>;
+}
+
+impl ParamValue for bool {
+    fn from_param(param: &ParamDef) -> Result<Self, Error> {
+        Ok(param.as_checkbox()?.value())
+    }
+}
+impl ParamValue for i32 {
+    fn from_param(param: &ParamDef) -> Result<Self, Error> {
+        match param.as_param()? {
+            Param::Slider(x) => Ok(x.value()),
+            Param::Popup(x)  => Ok(x.value()),
+            x => {
+                log::error!("Invalid param type! Requested an integer value, but the param is {:?}", x);
+                Err(Error::InvalidParms)
+            }
+        }
+    }
+}
+impl ParamValue for f64 {
+    fn from_param(param: &ParamDef) -> Result<Self, Error> {
+        match param.as_param()? {
+            Param::FloatSlider(x) => Ok(x.value()),
+            Param::Angle(x)       => Ok(x.degrees()),
+            x => {
+                log::error!("Invalid param type! Requested a float value, but the param is {:?}", x);
+                Err(Error::InvalidParms)
+            }
+        }
+    }
+}
+impl ParamValue for f32 {
+    fn from_param(param: &ParamDef) -> Result<Self, Error> {
+        f64::from_param(param).map(|x| x as f32)
+    }
+}
+impl ParamValue for Pixel8 {
+    fn from_param(param: &ParamDef) -> Result<Self, Error> {
+        Ok(param.as_color()?.value())
+    }
+}
+impl ParamValue for (f32, f32) {
+    fn from_param(param: &ParamDef) -> Result<Self, Error> {
+        Ok(param.as_point()?.value())
+    }
+}
+
+/// Reads a whole set of params into a user struct, so render code can start with a single call to [`Parameters::read()`].
+///
+/// ```ignore
+/// struct Settings { amount: f64, color: Pixel8, invert: bool }
+///
+/// impl FromParams<Params> for Settings {
+///     fn from_params(params: &Parameters<Params>) -> Result<Self, Error> {
+///         Ok(Self {
+///             amount: params.value(Params::Amount)?,
+///             color:  params.value(Params::Color)?,
+///             invert: params.value(Params::Invert)?,
+///         })
+///     }
+/// }
+///
+/// let settings: Settings = params.read()?;
+/// ```
+pub trait FromParams<P: Eq + PartialEq + Hash + Copy + Debug>: Sized {
+    fn from_params(params: &Parameters<P>) -> Result<Self, Error>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checked_out_params_keep_expected_type() {
+        let mut param_def: ae_sys::PF_ParamDef = unsafe { std::mem::zeroed() };
+        apply_expected_type(&mut param_def, 2, Some(ParamType::Slider));
+        assert_eq!(param_def.param_type, ae_sys::PF_Param_SLIDER);
+
+        // The input layer stays a layer.
+        let mut input: ae_sys::PF_ParamDef = unsafe { std::mem::zeroed() };
+        apply_expected_type(&mut input, 0, Some(ParamType::Slider));
+        assert_eq!(input.param_type, ae_sys::PF_Param_LAYER);
+    }
+
+    #[test]
+    fn cloned_def_does_not_dispose_default_again() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static DISPOSED: AtomicUsize = AtomicUsize::new(0);
+
+        let raw_in_data: ae_sys::PF_InData = unsafe { std::mem::zeroed() };
+        let mut def = ParamDef::new(InData::from_raw(&raw_in_data));
+        def.owned_default = Some((std::ptr::NonNull::dangling().as_ptr(), |_| { DISPOSED.fetch_add(1, Ordering::SeqCst); }));
+        let copy = def.clone();
+        drop(def);
+        drop(copy);
+        assert_eq!(DISPOSED.load(Ordering::SeqCst), 1);
+    }
+}
@@ -13,6 +13,24 @@ pub use ae_sys::PF_HALF_CHAN8 as HALF_CHANNEL8;
 pub use ae_sys::PF_MAX_CHAN16 as MAX_CHANNEL16;
 pub use ae_sys::PF_HALF_CHAN16 as HALF_CHANNEL16;
 
+// The pixel types are bindgen structs, so serde support is provided through remote definitions.
+// Use them on fields with `#[serde(with = "after_effects::Pixel8Serde")]`.
+macro_rules! define_pixel_serde {
+    ($name:ident, $pixel:literal, $channel:ty) => {
+        #[derive(serde::Serialize, serde::Deserialize)]
+        #[serde(remote = $pixel)]
+        pub struct $name {
+            pub alpha: $channel,
+            pub red: $channel,
+            pub green: $channel,
+            pub blue: $channel,
+        }
+    };
+}
+define_pixel_serde!(Pixel8Serde, "Pixel8", u8);
+define_pixel_serde!(Pixel16Serde, "Pixel16", u16);
+define_pixel_serde!(PixelF32Serde, "PixelF32", f32);
+
 pub fn pixel8_to_16(p: Pixel8) -> Pixel16 {
     fn convert_8_to_16(x: u8) -> u16 {
         (((x as u32 * ae_sys::PF_MAX_CHAN16) + ae_sys::PF_HALF_CHAN8) / ae_sys::PF_MAX_CHAN8) as u16
@@ -39,6 +57,116 @@ pub fn pixel16_to_8(p: Pixel16) -> Pixel8 {
     }
 }
 
+pub fn pixel8_to_f32(p: Pixel8) -> PixelF32 {
+    let scale = 1.0 / ae_sys::PF_MAX_CHAN8 as f32;
+    PixelF32 {
+        alpha: p.alpha as f32 * scale,
+        red:   p.red   as f32 * scale,
+        green: p.green as f32 * scale,
+        blue:  p.blue  as f32 * scale,
+    }
+}
+
+pub fn pixel16_to_f32(p: Pixel16) -> PixelF32 {
+    let scale = 1.0 / ae_sys::PF_MAX_CHAN16 as f32;
+    PixelF32 {
+        alpha: p.alpha as f32 * scale,
+        red:   p.red   as f32 * scale,
+        green: p.green as f32 * scale,
+        blue:  p.blue  as f32 * scale,
+    }
+}
+
+pub fn pixel_f32_to_8(p: PixelF32) -> Pixel8 {
+    fn convert(x: f32) -> u8 {
+        (x.clamp(0.0, 1.0) * ae_sys::PF_MAX_CHAN8 as f32).round() as u8
+    }
+
+    Pixel8 {
+        alpha: convert(p.alpha),
+        red:   convert(p.red),
+        green: convert(p.green),
+        blue:  convert(p.blue),
+    }
+}
+
+pub fn pixel_f32_to_16(p: PixelF32) -> Pixel16 {
+    fn convert(x: f32) -> u16 {
+        (x.clamp(0.0, 1.0) * ae_sys::PF_MAX_CHAN16 as f32).round() as u16
+    }
+
+    Pixel16 {
+        alpha: convert(p.alpha),
+        red:   convert(p.red),
+        green: convert(p.green),
+        blue:  convert(p.blue),
+    }
+}
+
+/// Common interface of [`Pixel8`], [`Pixel16`] and [`PixelF32`], for writing an algorithm once and running it at every bit depth.
+///
+/// Channels are exchanged as `[alpha, red, green, blue]` normalized to `0.0..=1.0` of the depth's maximum value.
+/// Float pixels are passed through unscaled and unclamped.
+pub trait AePixel: Copy {
+    type Channel: Copy;
+    /// The world type whose pixels have this layout.
+    const WORLD_TYPE: aegp::WorldType;
+    /// The value of a fully opaque/white channel.
+    const MAX: Self::Channel;
+
+    fn channels(&self) -> [f32; 4];
+    /// Builds a pixel from normalized channels, clamping and rounding for integer depths.
+    fn from_channels(channels: [f32; 4]) -> Self;
+
+    /// Rec. 709 luminance of the stored color in `0.0..=1.0`, with [`Layer::REC709_LUMA_WEIGHTS`].
+    ///
+    /// The color isn't linearized or unpremultiplied, matching how effects usually define luma.
+    fn luma(&self) -> f32 {
+        luma_with(self.channels(), Layer::REC709_LUMA_WEIGHTS)
+    }
+    /// Like [`luma()`](Self::luma) but with [`Layer::REC601_LUMA_WEIGHTS`], for standard definition content.
+    fn luma_rec601(&self) -> f32 {
+        luma_with(self.channels(), Layer::REC601_LUMA_WEIGHTS)
+    }
+}
+
+fn luma_with([_, red, green, blue]: [f32; 4], [wr, wg, wb]: [f32; 3]) -> f32 {
+    wr * red + wg * green + wb * blue
+}
+
+macro_rules! impl_ae_pixel_int {
+    ($pixel:ty, $channel:ty, $world_type:ident, $max:expr) => {
+        impl AePixel for $pixel {
+            type Channel = $channel;
+            const WORLD_TYPE: aegp::WorldType = aegp::WorldType::$world_type;
+            const MAX: $channel = $max as $channel;
+
+            fn channels(&self) -> [f32; 4] {
+                let scale = 1.0 / $max as f32;
+                [self.alpha as f32 * scale, self.red as f32 * scale, self.green as f32 * scale, self.blue as f32 * scale]
+            }
+            fn from_channels([alpha, red, green, blue]: [f32; 4]) -> Self {
+                let convert = |x: f32| (x.clamp(0.0, 1.0) * $max as f32).round() as $channel;
+                Self { alpha: convert(alpha), red: convert(red), green: convert(green), blue: convert(blue) }
+            }
+        }
+    };
+}
+impl_ae_pixel_int!(Pixel8, u8, U8, ae_sys::PF_MAX_CHAN8);
+impl_ae_pixel_int!(Pixel16, u16, U15, ae_sys::PF_MAX_CHAN16);
+
+impl AePixel for PixelF32 {
+    type Channel = f32;
+    const WORLD_TYPE: aegp::WorldType = aegp::WorldType::F32;
+    const MAX: f32 = 1.0;
+
+    fn channels(&self) -> [f32; 4] {
+        [self.alpha, self.red, self.green, self.blue]
+    }
+    fn from_channels([alpha, red, green, blue]: [f32; 4]) -> Self {
+        Self { alpha, red, green, blue }
+    }
+}
 
 pub enum GenericPixel<'a> {
     Pixel8(&'a Pixel8),
@@ -107,3 +235,27 @@ impl<'a> GenericPixelMut<'a> {
         }
     }
 }
+
+/// A single value of a pixel, used to select what e.g. [`Layer::threshold`] looks at.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PixelChannel {
+    Alpha,
+    Red,
+    Green,
+    Blue,
+    /// Rec. 709 luminance of the stored color, see [`Layer::REC709_LUMA_WEIGHTS`].
+    Luminance,
+}
+
+impl PixelChannel {
+    /// Returns the value of this channel in `p`.
+    pub fn value(self, p: PixelF32) -> f32 {
+        match self {
+            Self::Alpha => p.alpha,
+            Self::Red   => p.red,
+            Self::Green => p.green,
+            Self::Blue  => p.blue,
+            Self::Luminance => p.luma(),
+        }
+    }
+}
@@ -69,8 +69,20 @@ impl PreRenderExtra {
         assert!(!self.as_ref().input.is_null());
         unsafe { (*self.as_ref().input).device_index as usize }
     }
+    /// Stores `val` for [`SmartRenderExtra::pre_render_data()`]. It's freed by After Effects once the render is done.
+    ///
+    /// Any data set previously is disposed first.
     pub fn set_pre_render_data<T: Any>(&mut self, val: T) {
         let boxed: Box<Box<dyn Any>> = Box::new(Box::new(val));
+        unsafe {
+            let output = &mut *self.as_mut().output;
+            if !output.pre_render_data.is_null() {
+                if let Some(delete_func) = output.delete_pre_render_data_func {
+                    delete_func(output.pre_render_data);
+                }
+                output.pre_render_data = std::ptr::null_mut();
+            }
+        }
         unsafe {
             (*self.as_mut().output).pre_render_data =
                 Box::<Box<dyn Any>>::into_raw(boxed) as *mut _;
@@ -79,6 +91,19 @@ impl PreRenderExtra {
             (*self.as_mut().output).delete_pre_render_data_func = Some(delete_pre_render_data);
         }
     }
+    /// Returns the data stored with [`set_pre_render_data()`](Self::set_pre_render_data), if any.
+    pub fn pre_render_data<T: Any>(&self) -> Option<&T> {
+        assert!(!self.as_ref().output.is_null());
+        let data = unsafe { (*self.as_ref().output).pre_render_data };
+        if data.is_null() {
+            return None;
+        }
+        let data = unsafe { &*(data as *const Box<dyn Any>) };
+        match data.downcast_ref::<T>() {
+            Some(data) => Some(data),
+            None => panic!("Invalid type for pre_render_data"),
+        }
+    }
     pub fn callbacks(&self) -> PreRenderCallbacks {
         unsafe { PreRenderCallbacks::from_raw(self.in_data_ptr, (*self.ptr).cb) }
     }
@@ -110,6 +135,14 @@ impl PreRenderExtra {
         }
     }
 
+    /// Tells After Effects that every pixel in the result rect has the same color, which allows it to skip some work downstream.
+    pub fn set_solid(&mut self, val: bool) {
+        assert!(!self.as_mut().output.is_null());
+        unsafe {
+            (*self.as_mut().output).solid = val as _;
+        }
+    }
+
     pub fn result_rect(&self) -> Rect {
         assert!(!self.as_ref().output.is_null());
         unsafe { (*self.as_ref().output).result_rect.into() }
@@ -141,6 +174,46 @@ impl PreRenderExtra {
         rect
     }
 }
+/// Unions the result rects of several checked out layers, for effects with multiple inputs.
+///
+/// Feed it the [`PF_CheckoutResult`](ae_sys::PF_CheckoutResult) of every [`PreRenderCallbacks::checkout_layer`] call during
+/// `SmartPreRender`, then pass the combined rects to [`PreRenderExtra`] with [`apply_to()`](Self::apply_to).
+/// Inputs with an empty result rect (e.g. an unset layer param) are ignored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ResultRectAccumulator {
+    result_rect: Rect,
+    max_result_rect: Rect,
+}
+impl Default for ResultRectAccumulator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl ResultRectAccumulator {
+    pub fn new() -> Self {
+        Self { result_rect: Rect::empty(), max_result_rect: Rect::empty() }
+    }
+    pub fn add(&mut self, checkout: &ae_sys::PF_CheckoutResult) -> &mut Self {
+        self.add_rects(checkout.result_rect.into(), checkout.max_result_rect.into())
+    }
+    pub fn add_rects(&mut self, result_rect: Rect, max_result_rect: Rect) -> &mut Self {
+        self.result_rect.union(&result_rect);
+        self.max_result_rect.union(&max_result_rect);
+        self
+    }
+    pub fn result_rect(&self) -> Rect {
+        self.result_rect
+    }
+    pub fn max_result_rect(&self) -> Rect {
+        self.max_result_rect
+    }
+    /// Sets the combined rects as the result rect and max result rect of `extra`, replacing what was set before.
+    pub fn apply_to(&self, extra: &mut PreRenderExtra) {
+        extra.set_result_rect(self.result_rect);
+        extra.set_max_result_rect(self.max_result_rect);
+    }
+}
+
 unsafe extern "C" fn delete_pre_render_data(data: *mut std::ffi::c_void) {
     if !data.is_null() {
         let _ = Box::<Box<dyn Any>>::from_raw(data as *mut _);
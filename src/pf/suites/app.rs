@@ -25,6 +25,15 @@ fn str_from_c(slice: &[i8]) -> Result<String, Error> {
     Ok(CStr::from_bytes_until_nul(slice).map_err(|_| Error::InvalidParms)?.to_string_lossy().into_owned())
 }
 
+fn app_color_to_pixel8(color: ae_sys::PF_App_Color) -> pf::Pixel8 {
+    pf::Pixel8 {
+        alpha: 255,
+        red:   (color.red   >> 8) as u8,
+        green: (color.green >> 8) as u8,
+        blue:  (color.blue  >> 8) as u8,
+    }
+}
+
 impl AppSuite {
     /// Acquire this suite from the host. Returns error if the suite is not available.
     /// Suite is released on drop.
@@ -37,6 +46,11 @@ impl AppSuite {
         call_suite_fn_single!(self, PF_AppGetBgColor -> ae_sys::PF_App_Color)
     }
 
+    /// Retrieves the current background color as an opaque 8-bit pixel, e.g. to use as the default fill of a generator.
+    pub fn bg_color8(&self) -> Result<pf::Pixel8, Error> {
+        Ok(app_color_to_pixel8(self.bg_color()?))
+    }
+
     /// Retrieves the color for the specified UI element. See [`AppColorType`] for a complete enumeration of available values.
     ///
     /// Basically any color in After Effects' UI can be retrieved.
@@ -44,6 +58,11 @@ impl AppSuite {
         call_suite_fn_single!(self, PF_AppGetColor -> ae_sys::PF_App_Color, color_type.into())
     }
 
+    /// Retrieves the color for the specified UI element as an opaque 8-bit pixel.
+    pub fn color8(&self, color_type: AppColorType) -> Result<pf::Pixel8, Error> {
+        Ok(app_color_to_pixel8(self.color(color_type)?))
+    }
+
     /// New in CC. Retrieves the active displayed language of AE UI so plug-in can match. Here are the possible language codes as of CC:
     ///
     /// - Chinese - `zh_CN`
@@ -99,6 +118,16 @@ impl AppSuite {
         call_suite_fn!(self, PF_SetCursor, cursor.into())
     }
 
+    /// Shows the [`CursorType::WatchNWait`] cursor until the returned [`BusyCursor`] is dropped, for long running work.
+    ///
+    /// When dropped, the cursor is handed back to After Effects with [`CursorType::None`]. The guard holds its own suite, so this
+    /// doesn't need an existing one: `AppSuite::busy_cursor()?`.
+    pub fn busy_cursor() -> Result<BusyCursor, Error> {
+        let suite = Self::new()?;
+        suite.set_cursor(CursorType::WatchNWait)?;
+        Ok(BusyCursor { suite })
+    }
+
     /// Returns `true` if After Effects is running in watched folder mode, or is a render engine installation.
     pub fn is_render_engine(&self) -> Result<bool, Error> {
         Ok(call_suite_fn_single!(self, PF_IsRenderEngine -> ae_sys::PF_Boolean)? != 0)
@@ -495,6 +524,16 @@ pub struct AppPersonalTextInfo {
     pub serial_str: String
 }
 
+/// Busy cursor shown while this is alive, returned from [`AppSuite::busy_cursor()`].
+pub struct BusyCursor {
+    suite: AppSuite,
+}
+impl Drop for BusyCursor {
+    fn drop(&mut self) {
+        let _ = self.suite.set_cursor(CursorType::None);
+    }
+}
+
 pub struct AppProgressDialog {
     suite_ptr: *const ae_sys::PFAppSuite6,
     ptr: ae_sys::PF_AppProgressDialogP,
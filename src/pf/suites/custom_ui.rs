@@ -1,20 +1,14 @@
 use crate::*;
 
-define_suite!(
-    EffectCustomUISuite,
-    PF_EffectCustomUISuite1,
-    kPFEffectCustomUISuite,
-    kPFEffectCustomUISuiteVersion1
-);
-
 // Premiere doesn't support the V2 version, and this suite is commonly used, so we don't want it failing even if we just want to use the `drawing_reference` function.
-// Define EffectCustomUISuite as version 1, but make it have the `context_async_manager` function, which is only available in V2
-// That function will then load V2 version of this suite instead
+// Acquire V2 where available and fall back to V1, whose only function is the first one of V2.
+// `context_async_manager` is only available in V2 and checks the acquired version.
 define_suite!(
-    EffectCustomUISuite2,
+    EffectCustomUISuite,
     PF_EffectCustomUISuite2,
     kPFEffectCustomUISuite,
-    kPFEffectCustomUISuiteVersion2
+    kPFEffectCustomUISuiteVersion2,
+    fallback: [kPFEffectCustomUISuiteVersion1]
 );
 
 impl EffectCustomUISuite {
@@ -33,10 +27,14 @@ impl EffectCustomUISuite {
     }
 
     /// Obtain the [`aegp::AsyncManager`].
+    ///
+    /// Requires version 2 of the suite, which Premiere doesn't provide. Returns [`Error::MissingSuite`] there.
     pub fn context_async_manager(&self, in_data: impl AsPtr<*const ae_sys::PF_InData>, extra: impl AsPtr<*mut ae_sys::PF_EventExtra>) -> Result<aegp::AsyncManager, Error> {
-        let v2suite = EffectCustomUISuite2::new()?;
+        if self.acquired_version() < ae_sys::kPFEffectCustomUISuiteVersion2 as i32 {
+            return Err(Error::MissingSuite);
+        }
         Ok(aegp::AsyncManager::from_raw(
-            call_suite_fn_single!(v2suite, PF_GetContextAsyncManager -> ae_sys::PF_AsyncManagerP, in_data.as_ptr() as *mut _, extra.as_ptr())?
+            call_suite_fn_single!(self, PF_GetContextAsyncManager -> ae_sys::PF_AsyncManagerP, in_data.as_ptr() as *mut _, extra.as_ptr())?
         ))
     }
 }
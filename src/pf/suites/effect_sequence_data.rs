@@ -19,4 +19,13 @@ impl EffectSequenceDataSuite {
     pub fn const_sequence_data(&self, effect_ref: impl AsPtr<ae_sys::PF_ProgPtr>) -> Result<ae_sys::PF_ConstHandle, Error> {
         call_suite_fn_single!(self, PF_GetConstSequenceData -> ae_sys::PF_ConstHandle, effect_ref.as_ptr())
     }
+
+    /// Retrieves the const sequence_data like [`const_sequence_data`](Self::const_sequence_data), copied out as bytes for effects that store flat data.
+    ///
+    /// The handle is owned by the host and only valid during the current call, so the contents are copied instead of borrowed.
+    pub fn const_sequence_data_bytes(&self, effect_ref: impl AsPtr<ae_sys::PF_ProgPtr>) -> Result<Vec<u8>, Error> {
+        // Not owned, so dropping the wrapper leaves the host's handle alone.
+        let handle = FlatHandle::from_raw(self.const_sequence_data(effect_ref)? as ae_sys::PF_Handle)?;
+        Ok(handle.to_vec())
+    }
 }
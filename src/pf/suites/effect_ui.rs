@@ -17,12 +17,11 @@ impl EffectUISuite {
 
     /// Changes the text on the options button in the effect controls palette.
     ///
-    /// Button name can be up to 31 characters.
+    /// Button name can be up to 30 bytes, longer names are truncated on a char boundary.
     ///
     /// NOTE: This must be called during [`Command::ParamSetup`].
     pub fn set_options_button_name(&self, effect_ref: impl AsPtr<ae_sys::PF_ProgPtr>, name: &str) -> Result<(), Error> {
-        assert!(name.len() < 31);
-        let name = std::ffi::CString::new(name).unwrap();
+        let name = std::ffi::CString::new(crate::truncate_str(name, 30)).unwrap();
         call_suite_fn!(self, PF_SetOptionsButtonName, effect_ref.as_ptr(), name.as_ptr())
     }
 }
@@ -23,12 +23,12 @@ impl GPUDeviceSuite {
     }
 
     /// This will return the device info with given device index, which includes necessary context/queue information
-    /// needed to dispatch task to the device. Refer PF_GPUDeviceInfo for details.
+    /// needed to dispatch task to the device. Refer [`GpuDeviceInfo`](pf::GpuDeviceInfo) for details.
     /// * `effect_ref`   - Effect reference from [`InData`](crate::InData::effect_ref).
     /// * `device_index` - The device index for the requested device.
     /// Returns the device info will to be filled.
-    pub fn device_info(&self, effect_ref: impl AsPtr<PF_ProgPtr>, device_index: usize) -> Result<ae_sys::PF_GPUDeviceInfo, Error> {
-        call_suite_fn_single!(self, GetDeviceInfo -> ae_sys::PF_GPUDeviceInfo, effect_ref.as_ptr(), device_index as _)
+    pub fn device_info(&self, effect_ref: impl AsPtr<PF_ProgPtr>, device_index: usize) -> Result<pf::GpuDeviceInfo, Error> {
+        Ok(call_suite_fn_single!(self, GetDeviceInfo -> ae_sys::PF_GPUDeviceInfo, effect_ref.as_ptr(), device_index as _)?.into())
     }
 
     /// Acquire/release exclusive access to `device_index`. All calls below this point generally require access be held.
@@ -47,6 +47,18 @@ impl HandleSuite {
         call_suite_fn_no_err!(self, host_get_handle_size, pf_handle)
     }
 
+    /// Like [`handle_size()`](Self::handle_size), but returns an error for a null handle or a size that doesn't fit into memory,
+    /// instead of a bogus size.
+    pub fn checked_handle_size(&self, pf_handle: PF_Handle) -> Result<usize, Error> {
+        if pf_handle.is_null() {
+            return Err(Error::BadCallbackParameter);
+        }
+        usize::try_from(self.handle_size(pf_handle))
+            .ok()
+            .filter(|size| *size <= isize::MAX as usize)
+            .ok_or(Error::InternalStructDamaged)
+    }
+
     /// Resize the handle.
     pub fn resize_handle(&self, new_size: usize, handle: *mut PF_Handle) -> Result<(), Error> {
         call_suite_fn!(self, host_resize_handle, new_size as A_HandleSize, handle)
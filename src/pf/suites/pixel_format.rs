@@ -16,14 +16,26 @@ impl PixelFormatSuite {
         crate::Suite::new()
     }
 
+    /// Adds a pixel format to the list of formats the effect can render in. Call during [`Command::GlobalSetup`](pf::Command::GlobalSetup), after [`clear_supported_pixel_formats()`](Self::clear_supported_pixel_formats).
     pub fn add_supported_pixel_format(&self, effect_ref: impl AsPtr<PF_ProgPtr>, pixel_format: pr::PixelFormat) -> Result<(), Error> {
         call_suite_fn!(self, AddSupportedPixelFormat, effect_ref.as_ptr(), pixel_format.into())
     }
 
+    /// Clears the list of pixel formats the effect can render in. By default only 8-bit BGRA is supported.
     pub fn clear_supported_pixel_formats(&self, effect_ref: impl AsPtr<PF_ProgPtr>) -> Result<(), Error> {
         call_suite_fn!(self, ClearSupportedPixelFormats, effect_ref.as_ptr())
     }
 
+    /// Replaces the list of pixel formats the effect can render in with `pixel_formats`.
+    pub fn set_supported_pixel_formats(&self, effect_ref: impl AsPtr<PF_ProgPtr>, pixel_formats: &[pr::PixelFormat]) -> Result<(), Error> {
+        let effect_ref = effect_ref.as_ptr();
+        self.clear_supported_pixel_formats(effect_ref)?;
+        for pixel_format in pixel_formats {
+            self.add_supported_pixel_format(effect_ref, *pixel_format)?;
+        }
+        Ok(())
+    }
+
     pub fn new_world_of_pixel_format(&self, in_data: impl AsPtr<*const PF_InData>, width: u32, height: u32, flags: pf::NewWorldFlags, pixel_format: pr::PixelFormat) -> Result<Layer, Error> {
         let layer = call_suite_fn_single!(self, NewWorldOfPixelFormat -> ae_sys::PF_EffectWorld, (*in_data.as_ptr()).effect_ref, width, height, flags.bits(), pixel_format.into())?;
         Ok(Layer::from_owned(layer, in_data, |self_layer| {
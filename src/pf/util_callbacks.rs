@@ -503,7 +503,13 @@ impl UtilCallbacks {
 
     // fn get_callback_addr( effect_ref: PF_ProgPtr, quality: PF_Quality, mode_flags: PF_ModeFlags, which_callback: PF_CallbackID, fn_ptr: *mut PF_CallbackFunc) -> PF_Err,
     // fn app(arg1: PF_ProgPtr, arg2: A_long, ...) -> PF_Err,
-    // ansi: PF_ANSICallbacks,
+
+    /// The host's implementations of the standard math functions, so results match After Effects on every platform.
+    pub fn ansi(&self) -> AnsiCallbacks {
+        unsafe {
+            AnsiCallbacks { utils: (*self.0.as_ptr()).utils }
+        }
+    }
 }
 
 #[derive(Default)]
@@ -835,3 +841,54 @@ impl Drop for Sampling {
         }
     }
 }
+
+macro_rules! ansi_fn {
+    ($(#[$attr:meta])* $name:ident($x:ident) => $fallback:expr) => {
+        $(#[$attr])*
+        pub fn $name(&self, $x: f64) -> f64 {
+            match unsafe { self.utils.as_ref() }.and_then(|utils| utils.ansi.$name) {
+                Some(f) => unsafe { f($x) },
+                None    => $fallback,
+            }
+        }
+    };
+    ($(#[$attr:meta])* $name:ident($x:ident, $y:ident) => $fallback:expr) => {
+        $(#[$attr])*
+        pub fn $name(&self, $x: f64, $y: f64) -> f64 {
+            match unsafe { self.utils.as_ref() }.and_then(|utils| utils.ansi.$name) {
+                Some(f) => unsafe { f($x, $y) },
+                None    => $fallback,
+            }
+        }
+    };
+}
+
+/// Standard math functions as implemented by the host, for effects whose output must be bit-identical across platforms, e.g. to stay consistent with cached frames.
+///
+/// Available are `atan`, `atan2`, `ceil`, `cos`, `exp`, `fabs`, `floor`, `fmod`, `hypot`, `log`, `log10`, `pow`, `sin`, `sqrt`, `tan`, `asin` and `acos`.
+/// If the host doesn't provide a function, the implementation from `std` is used.
+pub struct AnsiCallbacks {
+    utils: *const _PF_UtilCallbacks,
+}
+impl AnsiCallbacks {
+    ansi_fn!(atan(x)      => x.atan());
+    ansi_fn!(
+        /// Returns the angle of the vector (`x`, `y`) in radians.
+        atan2(y, x)       => y.atan2(x)
+    );
+    ansi_fn!(ceil(x)      => x.ceil());
+    ansi_fn!(cos(x)       => x.cos());
+    ansi_fn!(exp(x)       => x.exp());
+    ansi_fn!(fabs(x)      => x.abs());
+    ansi_fn!(floor(x)     => x.floor());
+    ansi_fn!(fmod(x, y)   => x % y);
+    ansi_fn!(hypot(x, y)  => x.hypot(y));
+    ansi_fn!(log(x)       => x.ln());
+    ansi_fn!(log10(x)     => x.log10());
+    ansi_fn!(pow(x, y)    => x.powf(y));
+    ansi_fn!(sin(x)       => x.sin());
+    ansi_fn!(sqrt(x)      => x.sqrt());
+    ansi_fn!(tan(x)       => x.tan());
+    ansi_fn!(asin(x)      => x.asin());
+    ansi_fn!(acos(x)      => x.acos());
+}
@@ -21,6 +21,11 @@ macro_rules! define_effect {
             plugin_instance: $global_type
         }
 
+        /// Effect-wide state, shared by all instances of the effect.
+        ///
+        /// The global type is created with `Default` during `GlobalSetup`, stored in a host handle in `global_data`,
+        /// passed as `&mut self` to every command and dropped during `GlobalSetdown`. There is no need to touch
+        /// `global_data` manually; keep lookup tables and other state that outlives a single render here.
         trait AdobePluginGlobal : Default {
             fn can_load(host_name: &str, host_version: &str) -> bool;
 
@@ -28,6 +33,7 @@ macro_rules! define_effect {
 
             fn handle_command(&mut self, command: Command, in_data: InData, out_data: OutData, params: &mut Parameters<$params_type>) -> Result<(), Error>;
         }
+        /// Per-instance state, stored in the sequence data and flattened to the project when saving.
         trait AdobePluginInstance : Default {
             fn flatten(&self) -> Result<(u16, Vec<u8>), Error>;
             fn unflatten(version: u16, serialized: &[u8]) -> Result<Self, Error>;
@@ -106,7 +112,11 @@ macro_rules! define_effect {
             let in_data = InData::from_raw(in_data_ptr);
             let out_data = OutData::from_raw(out_data_ptr);
 
-            let cmd = RawCommand::from(cmd);
+            // Newer hosts may send commands this crate doesn't know about yet, ignore them instead of panicking.
+            let Some(cmd) = RawCommand::try_from_raw(cmd) else {
+                $crate::log::warn!("Ignoring unknown command {cmd}");
+                return Ok(());
+            };
 
             // Allocate or restore global data pointer
             let mut global_handle = if cmd == RawCommand::GlobalSetup {